@@ -0,0 +1,324 @@
+//! Declarative achievements, unlocked from `GameEvent`s, remembered in
+//! `localStorage` and announced with a toast on the game's canvas. In a
+//! two-player game either player's events count, as both are at the
+//! keyboard; a run a bot played any part of doesn't count.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use web_sys::{window, CanvasRenderingContext2d, Storage};
+
+use crate::events::GameEvent;
+use crate::game::GameKind;
+use crate::locale::{fill, Language};
+use crate::theme::Style;
+
+const STORAGE_KEY: &str = "wasm_games.achievements";
+const TOAST_DURATION: f64 = 3.0; // ✅ Seconds each unlock stays on screen
+
+/// What has to happen for an achievement to unlock.
+#[derive(Clone, Copy, Debug)]
+pub enum Rule {
+    PipesPassed(u32),
+    SnakeLength(usize),
+    MazeCleared { without_stopping: bool },
+    GhostStreak(u32),
+}
+
+impl Rule {
+    fn is_met_by(&self, event: &GameEvent) -> bool {
+        match (*self, event) {
+            (Rule::PipesPassed(goal), GameEvent::PipePassed { total }) => *total >= goal,
+            (Rule::SnakeLength(goal), GameEvent::SnakeGrew { length }) => *length >= goal,
+            (Rule::MazeCleared { without_stopping }, GameEvent::MazeCleared { stopped }) => {
+                !without_stopping || !stopped
+            }
+            (Rule::GhostStreak(goal), GameEvent::GhostEaten { streak }) => *streak >= goal,
+            _ => false,
+        }
+    }
+}
+
+/// Titles and descriptions are in the string table (`locale.rs`), in the
+/// order of `ACHIEVEMENTS`.
+pub struct Achievement {
+    pub id: &'static str,
+    pub game: GameKind,
+    pub rule: Rule,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "flappy-first-pipe",
+        game: GameKind::Flappy,
+        rule: Rule::PipesPassed(1),
+    },
+    Achievement {
+        id: "flappy-50-pipes",
+        game: GameKind::Flappy,
+        rule: Rule::PipesPassed(50),
+    },
+    Achievement {
+        id: "snake-length-25",
+        game: GameKind::Snake,
+        rule: Rule::SnakeLength(25),
+    },
+    Achievement {
+        id: "snake-length-100",
+        game: GameKind::Snake,
+        rule: Rule::SnakeLength(100),
+    },
+    Achievement {
+        id: "pacman-clear",
+        game: GameKind::Pacman,
+        rule: Rule::MazeCleared { without_stopping: false },
+    },
+    Achievement {
+        id: "pacman-clear-nonstop",
+        game: GameKind::Pacman,
+        rule: Rule::MazeCleared { without_stopping: true },
+    },
+    Achievement {
+        id: "pacman-four-ghosts",
+        game: GameKind::Pacman,
+        rule: Rule::GhostStreak(4),
+    },
+];
+
+/// Unlock time (ms since the epoch) by achievement id.
+type Unlocks = BTreeMap<String, f64>;
+
+fn storage() -> Option<Storage> {
+    window()?.local_storage().ok()?
+}
+
+fn load_unlocks() -> Unlocks {
+    storage()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok()?)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_unlocks(unlocks: &Unlocks) {
+    if let (Some(storage), Ok(json)) = (storage(), serde_json::to_string(unlocks)) {
+        storage.set_item(STORAGE_KEY, &json).ok();
+    }
+}
+
+/// Per-game achievement tracking: checks events and shows unlock toasts.
+pub struct Achievements {
+    unlocked: Unlocks,
+    /// Index into `ACHIEVEMENTS` of each unlock still to show, with its time left.
+    toasts: VecDeque<(usize, f64)>,
+    /// A bot is playing player 1.
+    bot: bool,
+    /// A bot has played some of the current run.
+    assisted: bool,
+}
+
+impl Achievements {
+    pub fn load() -> Achievements {
+        Achievements {
+            unlocked: load_unlocks(),
+            toasts: VecDeque::new(),
+            bot: false,
+            assisted: false,
+        }
+    }
+
+    pub fn set_bot(&mut self, bot: bool) {
+        self.bot = bot;
+        self.assisted |= bot;
+    }
+
+    pub fn record(&mut self, event: &GameEvent) {
+        for index in self.newly_met(event) {
+            let achievement = &ACHIEVEMENTS[index];
+            // ✅ Other games on the page may have unlocked things since we loaded
+            self.unlocked = load_unlocks();
+            self.unlocked
+                .entry(achievement.id.to_string())
+                .or_insert_with(js_sys::Date::now);
+            save_unlocks(&self.unlocked);
+
+            self.toasts.push_back((index, TOAST_DURATION));
+        }
+    }
+
+    /// Indices into `ACHIEVEMENTS` of those `event` unlocks that aren't
+    /// unlocked yet.
+    fn newly_met(&mut self, event: &GameEvent) -> Vec<usize> {
+        if *event == GameEvent::Started {
+            self.assisted = self.bot;
+        }
+        if self.assisted {
+            return Vec::new();
+        }
+        ACHIEVEMENTS
+            .iter()
+            .enumerate()
+            .filter(|(_, achievement)| !self.unlocked.contains_key(achievement.id) && achievement.rule.is_met_by(event))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn update(&mut self, delta_time: f64) {
+        if let Some((_, remaining)) = self.toasts.front_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.toasts.pop_front();
+            }
+        }
+    }
+
+    pub fn render(&self, context: &CanvasRenderingContext2d, width: f64, height: f64, style: &Style) {
+        let Some(&(index, _)) = self.toasts.front() else {
+            return;
+        };
+        let (palette, strings) = (style.palette(), style.strings());
+        let (title, _) = strings.achievements[index];
+
+        context.set_fill_style_str(palette.panel);
+        context.fill_rect(0.0, height - 48.0, width, 48.0);
+        context.set_fill_style_str(palette.highlight);
+        context.set_font("16px monospace");
+        context.set_text_align("center");
+        context
+            .fill_text(&fill(strings.achievement_unlocked, &[&title]), width / 2.0, height - 18.0)
+            .unwrap();
+    }
+}
+
+#[derive(Serialize)]
+struct TrophyCaseEntry {
+    id: &'static str,
+    game: GameKind,
+    title: &'static str,
+    description: &'static str,
+    unlocked_at: Option<f64>,
+}
+
+/// Every achievement with its unlock time (or `null`), for a trophy case page,
+/// titled in the first of `lang`'s language tags bundled (else English).
+#[wasm_bindgen]
+pub fn achievements(lang: Option<String>) -> Result<JsValue, JsValue> {
+    let unlocked = load_unlocks();
+    let strings = Language::negotiate(lang.as_deref().unwrap_or_default()).strings();
+    let entries: Vec<TrophyCaseEntry> = ACHIEVEMENTS
+        .iter()
+        .zip(strings.achievements)
+        .map(|(achievement, (title, description))| TrophyCaseEntry {
+            id: achievement.id,
+            game: achievement.game,
+            title,
+            description,
+            unlocked_at: unlocked.get(achievement.id).copied(),
+        })
+        .collect();
+
+    let json = serde_json::to_string(&entries).map_err(|err| JsError::new(&err.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent, new_sim, Command, GameConfig, TICK};
+
+    fn achievements() -> Achievements {
+        Achievements { unlocked: Unlocks::new(), toasts: VecDeque::new(), bot: false, assisted: false }
+    }
+
+    fn ids(indices: Vec<usize>) -> Vec<&'static str> {
+        indices.into_iter().map(|index| ACHIEVEMENTS[index].id).collect()
+    }
+
+    #[test]
+    fn each_rule_is_met_from_its_threshold_on() {
+        let pipes = |total| GameEvent::PipePassed { total };
+        assert!(!Rule::PipesPassed(50).is_met_by(&pipes(49)));
+        assert!(Rule::PipesPassed(50).is_met_by(&pipes(50)));
+        assert!(Rule::PipesPassed(50).is_met_by(&pipes(51)));
+
+        let grew = |length| GameEvent::SnakeGrew { length };
+        assert!(!Rule::SnakeLength(25).is_met_by(&grew(24)));
+        assert!(Rule::SnakeLength(25).is_met_by(&grew(25)));
+
+        let ghosts = |streak| GameEvent::GhostEaten { streak };
+        assert!(!Rule::GhostStreak(4).is_met_by(&ghosts(3)));
+        assert!(Rule::GhostStreak(4).is_met_by(&ghosts(4)));
+
+        for stopped in [false, true] {
+            let cleared = GameEvent::MazeCleared { stopped };
+            assert!(Rule::MazeCleared { without_stopping: false }.is_met_by(&cleared));
+            assert_eq!(Rule::MazeCleared { without_stopping: true }.is_met_by(&cleared), !stopped);
+        }
+
+        // ✅ Only the event a rule counts, however big the number
+        assert!(!Rule::PipesPassed(1).is_met_by(&grew(100)));
+        assert!(!Rule::SnakeLength(1).is_met_by(&GameEvent::GameOver { score: 1000 }));
+    }
+
+    #[test]
+    fn each_unlocks_once() {
+        let mut achievements = achievements();
+        let both = ids(achievements.newly_met(&GameEvent::PipePassed { total: 50 }));
+        assert_eq!(both, ["flappy-first-pipe", "flappy-50-pipes"]);
+        achievements.unlocked.insert("flappy-first-pipe".to_string(), 0.0);
+        assert_eq!(ids(achievements.newly_met(&GameEvent::PipePassed { total: 51 })), ["flappy-50-pipes"]);
+    }
+
+    #[test]
+    fn two_players_count_their_own_pipes_not_the_sum() {
+        // ✅ Both birds flap together, so each clears every pipe
+        let config = GameConfig::from_lookup(|key| match key {
+            "game" => Some("flappy".to_string()),
+            "players" => Some("2".to_string()),
+            "seed" => Some("1".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        let (mut sim, mut bot, mut achievements) = (new_sim(&config), agent("greedy", 0).unwrap(), achievements());
+        let mut unlocked = Vec::new();
+        while sim.scores().iter().sum::<u32>() < 60 {
+            if let Some(Command::Flap(0)) = bot.act(&sim.view()) {
+                sim.command(Command::Flap(0));
+                sim.command(Command::Flap(1));
+            }
+            sim.update(TICK);
+            for event in sim.take_events() {
+                assert!(!matches!(event, GameEvent::GameOver { .. }), "the birds crashed at {:?}", sim.scores());
+                for id in ids(achievements.newly_met(&event)) {
+                    achievements.unlocked.insert(id.to_string(), 0.0);
+                    unlocked.push(id);
+                }
+            }
+        }
+        assert_eq!(sim.scores(), [30, 30]);
+        assert_eq!(unlocked, ["flappy-first-pipe"]);
+    }
+
+    #[test]
+    fn runs_a_bot_played_any_of_dont_count() {
+        let mut achievements = achievements();
+        let pipe = GameEvent::PipePassed { total: 1 };
+
+        achievements.set_bot(true);
+        achievements.newly_met(&GameEvent::Started);
+        assert!(achievements.newly_met(&pipe).is_empty());
+
+        // ✅ Taking the bot off mid-run doesn't make the rest of it count...
+        achievements.set_bot(false);
+        assert!(achievements.newly_met(&pipe).is_empty());
+
+        // ✅ ...but the next run does, unless a bot joins it partway
+        achievements.newly_met(&GameEvent::Started);
+        achievements.set_bot(true);
+        achievements.set_bot(false);
+        assert!(achievements.newly_met(&pipe).is_empty());
+
+        achievements.newly_met(&GameEvent::Started);
+        assert_eq!(ids(achievements.newly_met(&pipe)), ["flappy-first-pipe"]);
+    }
+}
@@ -0,0 +1,257 @@
+//! Bots that play from the same `View` a spectator sees, one command at a
+//! time. Player 1's seat only. `Game::set_agent` lets one drive a live game,
+//! `src/bin/simulate.rs` runs them in bulk.
+
+use std::sync::OnceLock;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use wasm_bindgen::JsValue;
+
+use crate::flappy::{self, FlappyView};
+use crate::pacman::PacmanView;
+use crate::path::{self, Search, DIRECTIONS};
+use crate::sim::Command;
+use crate::snake::SnakeView;
+use crate::view::View;
+
+/// Names accepted by `agent()`. `greedy` is the built-in AI for whichever
+/// game it's given: the flappy autopilot, the snake solver (A* with a
+/// Hamiltonian cycle to fall back on) or the ghost-avoiding Pac-Man agent.
+pub const AGENTS: [&str; 2] = ["random", "greedy"];
+
+/// Ticks between a random bot's moves.
+const RANDOM_INTERVAL: u32 = 10;
+/// Ticks a flap takes to stop climbing, roughly; flapping sooner overshoots.
+const FLAP_COOLDOWN: u32 = 8;
+
+pub trait Agent {
+    /// What to do before the next tick, if anything.
+    fn act(&mut self, view: &View) -> Option<Command>;
+}
+
+/// `AGENTS` the way an error message lists them, e.g. "random or greedy".
+pub(crate) fn agent_names() -> &'static str {
+    static NAMES: OnceLock<String> = OnceLock::new();
+    NAMES.get_or_init(|| AGENTS.join(" or "))
+}
+
+/// A built-in bot by name (see `AGENTS`); `seed` drives any randomness it has.
+pub fn agent(name: &str, seed: u64) -> Option<Box<dyn Agent>> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "random" => Some(Box::new(RandomAgent { rng: StdRng::seed_from_u64(seed), wait: 0 })),
+        "greedy" => Some(Box::new(GreedyAgent::default())),
+        _ => None,
+    }
+}
+
+/// A bot written in JS: called with each frame's `View` as JSON, it returns
+/// a `Command` as JSON (`{"flap":0}`, `{"steer":[0,1,0]}`) or nothing.
+pub(crate) struct JsAgent(pub(crate) js_sys::Function);
+
+impl Agent for JsAgent {
+    fn act(&mut self, view: &View) -> Option<Command> {
+        let view = serde_json::to_string(view).expect("views serialize");
+        let command = self.0.call1(&JsValue::NULL, &JsValue::from_str(&view)).ok()?.as_string()?;
+        serde_json::from_str(&command).ok()
+    }
+}
+
+/// Mashes buttons: a random flap or direction every few ticks.
+struct RandomAgent {
+    rng: StdRng,
+    wait: u32,
+}
+
+impl Agent for RandomAgent {
+    fn act(&mut self, view: &View) -> Option<Command> {
+        if self.wait > 0 {
+            self.wait -= 1;
+            return None;
+        }
+        self.wait = RANDOM_INTERVAL;
+
+        match view {
+            View::Flappy(_) => self.rng.gen_bool(0.5).then_some(Command::Flap(0)),
+            View::Snake(_) | View::Pacman(_) => {
+                let (dx, dy) = DIRECTIONS[self.rng.gen_range(0..DIRECTIONS.len() as u32) as usize];
+                Some(Command::Steer(0, dx, dy))
+            }
+        }
+    }
+}
+
+/// The three built-in AIs behind one name, picked by the view each frame:
+/// the flappy autopilot flaps for the next pipe gap (`flap`), the snake
+/// solver runs `snake_step` and the Pac-Man agent `pacman_step`. Each goes
+/// for the nearest goal, keeping clear of what would end the run.
+#[derive(Default)]
+struct GreedyAgent {
+    /// Ticks since the last flap, `None` before the first.
+    since_flap: Option<u32>,
+    /// Where the snake's head or Pac-Man was when we last steered.
+    last_position: Option<(usize, usize)>,
+}
+
+impl Agent for GreedyAgent {
+    fn act(&mut self, view: &View) -> Option<Command> {
+        match view {
+            View::Flappy(view) => self.flap(view),
+            View::Snake(view) => {
+                let head = view.snakes[0][0];
+                let head = (head.0 as usize, head.1 as usize);
+                self.steer(head, view.hud.result.is_some(), || snake_step(view))
+            }
+            View::Pacman(view) => self.steer(view.pacman, view.hud.result.is_some(), || pacman_step(view)),
+        }
+    }
+}
+
+impl GreedyAgent {
+    fn flap(&mut self, view: &FlappyView) -> Option<Command> {
+        let y = view.birds[0]?;
+
+        // ✅ Aim a little below the middle of the next gap; a flap jumps up
+        let target = view
+            .pipes
+            .iter()
+            .find(|&&(x, _)| x + flappy::PIPE_WIDTH >= flappy::BIRD_LEFT)
+            .map_or(flappy::HEIGHT / 2.0, |&(_, gap_y)| gap_y + view.pipe_gap * 0.65);
+
+        // ✅ The first flap, or one after a game over, starts the run
+        let waiting = self.since_flap.is_none() || view.hud.result.is_some();
+        let ready = self.since_flap.is_some_and(|ticks| ticks >= FLAP_COOLDOWN);
+        if waiting || (y > target && ready) {
+            self.since_flap = Some(0);
+            return Some(Command::Flap(0));
+        }
+        self.since_flap = self.since_flap.map(|ticks| ticks + 1);
+        None
+    }
+
+    /// Only decides again once the last move has happened, or to start a run.
+    fn steer(
+        &mut self,
+        position: (usize, usize),
+        waiting: bool,
+        step: impl FnOnce() -> (i32, i32),
+    ) -> Option<Command> {
+        if self.last_position == Some(position) && !waiting {
+            return None;
+        }
+        self.last_position = Some(position);
+        let (dx, dy) = step();
+        Some(Command::Steer(0, dx, dy))
+    }
+}
+
+/// A* to the food as long as the snake stays in order round a Hamiltonian
+/// cycle of the board: no move goes past the food or its own tail along the
+/// cycle, so every tile between head and tail is clear and it can't box
+/// itself in. When A* would break that order, the step furthest round the
+/// cycle that keeps it; once in order that fills the board. Boards with no
+/// cycle (both sides odd) fall back on A* while the snake still has room
+/// to move once it gets there, and failing that the move with the most room.
+fn snake_step(view: &SnakeView) -> (i32, i32) {
+    let (width, height) = (view.width as usize, view.height as usize);
+    let mut blocked = vec![false; width * height];
+    for body in &view.snakes {
+        // ✅ Tails move out of the way, so they don't count; a neck does, as snakes can't turn back
+        for &(x, y) in &body[..body.len().saturating_sub(1).max(2).min(body.len())] {
+            blocked[y as usize * width + x as usize] = true;
+        }
+    }
+    let body = &view.snakes[0];
+    let head = (body[0].0 as usize, body[0].1 as usize);
+    let open = |(x, y): (usize, usize)| !blocked[y * width + x];
+    let fits = |(dx, dy): (i32, i32)| {
+        let next = ((head.0 as i32 + dx) as usize, (head.1 as i32 + dy) as usize);
+        Search::new((width, height), next, open).order.len() >= body.len()
+    };
+
+    let food = (view.food.0 as usize, view.food.1 as usize);
+    let a_star = path::a_star((width, height), head, food, open);
+    if let Some(cycle) = path::hamiltonian_cycle(width, height) {
+        if let Some(step) = cycle_step(&cycle, (width, height), body, food, a_star, open) {
+            return step;
+        }
+    }
+    if let Some(step) = a_star.filter(|&step| fits(step)) {
+        return step;
+    }
+
+    let search = Search::new((width, height), head, open);
+    let backwards = body.get(1).map(|&(x, y)| (x as i32 - head.0 as i32, y as i32 - head.1 as i32));
+    DIRECTIONS
+        .into_iter()
+        .filter(|&step| Some(step) != backwards)
+        .max_by_key(|&step| search.room(step))
+        .unwrap_or((1, 0))
+}
+
+/// The move `snake_step` makes on `cycle`, if any keeps the snake in order.
+fn cycle_step(
+    cycle: &[(usize, usize)],
+    (width, height): (usize, usize),
+    body: &[(u32, u32)],
+    food: (usize, usize),
+    a_star: Option<(i32, i32)>,
+    open: impl Fn((usize, usize)) -> bool,
+) -> Option<(i32, i32)> {
+    let mut index = vec![0; width * height];
+    for (i, &(x, y)) in cycle.iter().enumerate() {
+        index[y * width + x] = i;
+    }
+    let head = (body[0].0 as usize, body[0].1 as usize);
+    let tail = body[body.len() - 1];
+    // ✅ Tiles from the head to `tile` going round the cycle; a lone head is its
+    // own tail, a whole lap away
+    let ahead = |(x, y): (usize, usize)| (index[y * width + x] + cycle.len() - index[head.1 * width + head.0]) % cycle.len();
+    let (tail, food) = (ahead((tail.0 as usize, tail.1 as usize)), ahead(food));
+    let tail = if tail == 0 { cycle.len() } else { tail };
+
+    let next = |(dx, dy): (i32, i32)| {
+        let (x, y) = (head.0 as i32 + dx, head.1 as i32 + dy);
+        (x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height).then_some((x as usize, y as usize))
+    };
+    // ✅ Never past the food, so each move gets nearer it round the cycle, and
+    // never past the tail, which every tile before it is clear up to
+    let keeps_order = |step: (i32, i32)| {
+        next(step).is_some_and(|tile| open(tile) && ahead(tile) <= food && ahead(tile) <= tail)
+    };
+
+    if let Some(step) = a_star.filter(|&step| keeps_order(step)) {
+        return Some(step);
+    }
+    DIRECTIONS.into_iter().filter(|&step| keeps_order(step)).max_by_key(|&step| next(step).map(ahead))
+}
+
+/// Shortest way to the nearest pellet that doesn't pass next to a ghost
+/// (unless they're frightened); failing that, away from the nearest ghost.
+fn pacman_step(view: &PacmanView) -> (i32, i32) {
+    let rows: Vec<&[u8]> = view.rows.iter().map(|row| row.as_bytes()).collect();
+    let (width, height) = (rows[0].len(), rows.len());
+    let near_ghost = |(x, y): (usize, usize)| {
+        !view.frightened && view.ghosts.iter().any(|&(gx, gy)| gx.abs_diff(x) + gy.abs_diff(y) <= 1)
+    };
+
+    let search = Search::new((width, height), view.pacman, |(x, y)| rows[y][x] != b'#' && !near_ghost((x, y)));
+    let pellet = search.order.iter().find(|&&(x, y)| matches!(rows[y][x], b'.' | b'o'));
+    if let Some(step) = pellet.and_then(|&tile| search.first_step(tile)) {
+        return step;
+    }
+
+    let (x, y) = view.pacman;
+    let next = |(dx, dy): (i32, i32)| ((x as i32 + dx) as usize, (y as i32 + dy) as usize);
+    DIRECTIONS
+        .into_iter()
+        .filter(|&step| {
+            let (nx, ny) = next(step);
+            rows.get(ny).and_then(|row| row.get(nx)).is_some_and(|&tile| tile != b'#')
+        })
+        .max_by_key(|&step| {
+            let (nx, ny) = next(step);
+            view.ghosts.iter().map(|&(gx, gy)| gx.abs_diff(nx) + gy.abs_diff(ny)).min().unwrap_or(0)
+        })
+        .unwrap_or((0, 0))
+}
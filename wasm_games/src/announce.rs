@@ -0,0 +1,116 @@
+//! Spoken updates for screen readers: the canvas shows the game to sighted
+//! players only, so each game also reads out what changes through the page's
+//! ARIA live region (`utils::announce`): the start of a run, every few
+//! points, players going out and the final result.
+
+use crate::events::{DeathCause, GameEvent};
+use crate::game::{GameKind, RunResult};
+use crate::locale::{fill, Strings};
+use crate::sim::Sim;
+use crate::utils;
+use crate::view::View;
+
+pub(crate) struct Announcer {
+    game: GameKind,
+    /// Scores as last announced against, to spot a milestone being passed.
+    scores: Vec<u32>,
+}
+
+impl Announcer {
+    pub(crate) fn new(game: GameKind) -> Announcer {
+        Announcer { game, scores: Vec::new() }
+    }
+
+    /// Announces what `events` (this tick's) and the scores say changed.
+    pub(crate) fn update(&mut self, events: &[GameEvent], sim: &dyn Sim, strings: &Strings) {
+        let lines = self.lines(events, sim, strings);
+        if !lines.is_empty() {
+            utils::announce(&lines.join(" "));
+        }
+    }
+
+    fn lines(&mut self, events: &[GameEvent], sim: &dyn Sim, strings: &Strings) -> Vec<String> {
+        let mut lines = Vec::new();
+        let scores = sim.scores();
+        let versus = scores.len() > 1;
+
+        for event in events {
+            match *event {
+                GameEvent::Started => lines.push(fill(strings.started, &[&title(self.game, strings)])),
+                GameEvent::MazeCleared { .. } => lines.push(strings.maze_cleared.to_string()),
+                GameEvent::GhostEaten { .. } => lines.push(strings.ghost_eaten.to_string()),
+                GameEvent::Died { player, cause } if versus => {
+                    lines.push(fill(strings.player_out, &[&(player + 1), &cause_text(cause, strings)]));
+                }
+                GameEvent::Died { cause, .. } => lines.push(format!("{}.", capitalized(cause_text(cause, strings)))),
+                GameEvent::GameOver { score } => lines.push(result_text(sim, score, strings)),
+                GameEvent::PipePassed { .. } | GameEvent::SnakeGrew { .. } => {}
+            }
+        }
+
+        // ✅ Every `milestone` points; a lower score means a new run
+        let milestone = milestone(self.game);
+        for (player, &score) in scores.iter().enumerate() {
+            let last = self.scores.get(player).copied().unwrap_or(0);
+            if score > last && score / milestone > last / milestone {
+                lines.push(match versus {
+                    true => fill(strings.player_points, &[&(player + 1), &score]),
+                    false => fill(strings.points, &[&score]),
+                });
+            }
+        }
+        self.scores = scores;
+        lines
+    }
+}
+
+fn title(game: GameKind, strings: &Strings) -> &'static str {
+    match game {
+        GameKind::Flappy => strings.flappy_title,
+        GameKind::Snake => strings.snake_title,
+        GameKind::Pacman => strings.pacman_title,
+    }
+}
+
+fn milestone(game: GameKind) -> u32 {
+    match game {
+        GameKind::Flappy | GameKind::Snake => 10,
+        GameKind::Pacman => 100, // ✅ A point a pellet
+    }
+}
+
+fn cause_text(cause: DeathCause, strings: &Strings) -> &'static str {
+    match cause {
+        DeathCause::Pipe => strings.cause_pipe,
+        DeathCause::Ground => strings.cause_ground,
+        DeathCause::Ceiling => strings.cause_ceiling,
+        DeathCause::Wall => strings.cause_wall,
+        DeathCause::OwnBody => strings.cause_own_body,
+        DeathCause::OtherSnake => strings.cause_other_snake,
+        DeathCause::HeadOn => strings.cause_head_on,
+        DeathCause::Ghost => strings.cause_ghost,
+    }
+}
+
+fn capitalized(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
+
+/// The end-of-run banner, read out: the sim has already reset, but keeps the
+/// result on screen until the next run.
+fn result_text(sim: &dyn Sim, score: u32, strings: &Strings) -> String {
+    let hud = match sim.view() {
+        View::Flappy(view) => view.hud,
+        View::Snake(view) => view.hud,
+        View::Pacman(view) => view.hud,
+    };
+    match hud.result {
+        Some(RunResult::Winner(Some(player))) => fill(strings.over_player_wins, &[&(player + 1)]),
+        Some(RunResult::Winner(None)) => strings.over_draw.to_string(),
+        _ => match hud.challenge_score {
+            Some(target) if score > target => fill(strings.over_challenge_beaten, &[&score]),
+            _ => fill(strings.over_score, &[&score]),
+        },
+    }
+}
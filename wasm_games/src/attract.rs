@@ -0,0 +1,107 @@
+//! Attract mode: after `attract` seconds idle on the title screen, the greedy
+//! bot (`agent.rs`) plays a demo run under a blinking "PRESS START", like an
+//! arcade cabinet. Any input goes back to the title. The demo runs its own
+//! simulation, so it never touches the player's replays or achievements.
+//! With reduced motion the demo never starts by itself, and if one is already
+//! showing, "PRESS START" stays lit instead of blinking.
+
+use crate::agent::{self, Agent};
+use crate::config::GameConfig;
+use crate::draw::Canvas;
+use crate::sim::{self, Sim};
+use crate::theme::Style;
+
+const DEMO_AGENT: &str = "greedy";
+const BLINK_INTERVAL: f64 = 0.5;
+
+pub struct Attract {
+    delay: f64,
+    /// Seconds on the title screen without input.
+    idle: f64,
+    config: GameConfig,
+    demo: Option<Demo>,
+}
+
+struct Demo {
+    sim: Box<dyn Sim>,
+    agent: Box<dyn Agent>,
+    time: f64,
+}
+
+impl Attract {
+    /// `None` if the config turns attract mode off.
+    pub fn new(config: &GameConfig) -> Option<Attract> {
+        let delay = config.attract()?;
+
+        // ✅ A solo demo on a fresh seed each run, whatever the player set up
+        let mut demo_config = config.clone();
+        demo_config.players = 1;
+        demo_config.seed = None;
+        demo_config.challenge_score = None;
+
+        Some(Attract { delay, idle: 0.0, config: demo_config, demo: None })
+    }
+
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    pub fn active(&self) -> bool {
+        self.demo.is_some()
+    }
+
+    /// Counts idle time while the game is `waiting`, starting the demo once
+    /// it reaches the delay, and plays the demo on.
+    pub fn update(&mut self, delta_time: f64, waiting: bool) {
+        self.idle = if waiting { self.idle + delta_time } else { 0.0 };
+        if self.demo.is_none() && !self.config.style.reduced_motion && self.idle >= self.delay {
+            self.start();
+        }
+
+        let Some(demo) = &mut self.demo else {
+            return;
+        };
+        if let Some(command) = demo.agent.act(&demo.sim.view()) {
+            demo.sim.command(command);
+        }
+        demo.sim.update(delta_time);
+        demo.sim.take_events(); // ✅ Nothing counts in a demo
+        demo.time += delta_time;
+    }
+
+    pub fn start(&mut self) {
+        self.demo = Some(Demo {
+            sim: sim::new_sim(&self.config),
+            agent: agent::agent(DEMO_AGENT, rand::random()).expect("built-in agent"),
+            time: 0.0,
+        });
+    }
+
+    /// Any key or click: back to the title. Returns whether a demo was
+    /// showing, in which case the input is used up.
+    pub fn input(&mut self) -> bool {
+        self.idle = 0.0;
+        self.demo.take().is_some()
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.config.style = style;
+    }
+
+    /// Draws the demo, if one is showing, on `context`.
+    pub fn render(&self, context: &dyn Canvas) {
+        let Some(demo) = &self.demo else {
+            return;
+        };
+        let (view, style) = (demo.sim.view(), self.config.style());
+        view.draw(context, &style);
+
+        if style.reduced_motion || ((demo.time / BLINK_INTERVAL) as u64).is_multiple_of(2) {
+            let (width, height) = view.canvas_size();
+            context.set_fill_style_str(style.palette().text);
+            context.set_font("24px monospace");
+            context.set_text_align("center");
+            context.fill_text(style.strings().press_start, width as f64 / 2.0, height as f64 * 0.75).unwrap();
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Leaderboard server. Takes finished runs as input replays, plays each one
+//! back headlessly and only ranks the score if the replay really reaches it.
+//! Scores are kept one JSON object per line in a local file.
+//!
+//! ```text
+//! cargo run --bin leaderboard -- [ADDR] [--data FILE]
+//! ```
+//!
+//! then open `index.html?leaderboard=http://localhost:9002` and submit a run.
+//!
+//! - `POST /scores` with `{"name": "...", "replay": {...}}` (from `game.replay()`)
+//! - `GET /boards/{game}?limit=N`: best scores for flappy, snake or pacman
+//! - `GET /boards/{game}/seeds/{seed}?limit=N`: best scores on one seed
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use wasm_games::{GameKind, Replay};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:9002";
+const DEFAULT_DATA: &str = "leaderboard.jsonl";
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+const MAX_NAME_LEN: usize = 24;
+/// Largest submission accepted; an hour of frantic input is well under this.
+const MAX_BODY: u64 = 1 << 20;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Entry {
+    id: u64,
+    name: String,
+    game: String,
+    seed: u64,
+    score: u32,
+    /// The run's challenge token, so anyone can play the same seed and settings.
+    challenge: String,
+    submitted_at: u64,
+}
+
+#[derive(Deserialize)]
+struct Submission {
+    name: String,
+    replay: Replay,
+}
+
+struct Leaderboard {
+    entries: Vec<Entry>,
+    file: File,
+}
+
+impl Leaderboard {
+    fn open(path: &str) -> io::Result<Leaderboard> {
+        let mut entries = Vec::new();
+        if let Ok(file) = File::open(path) {
+            for (number, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str(&line) {
+                    Ok(entry) => entries.push(entry),
+                    Err(err) => eprintln!("leaderboard: {}:{}: skipping bad entry: {}", path, number + 1, err),
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Leaderboard { entries, file })
+    }
+
+    fn add(&mut self, entry: Entry) -> io::Result<()> {
+        let line = serde_json::to_string(&entry).expect("entries serialize");
+        writeln!(self.file, "{}", line)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Best first; ties go to whoever got there first.
+    fn top(&self, game: GameKind, seed: Option<u64>, limit: usize) -> Vec<&Entry> {
+        let mut entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.game == game.name() && seed.is_none_or(|seed| entry.seed == seed))
+            .collect();
+        entries.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+        entries.truncate(limit);
+        entries
+    }
+
+    fn submit(&mut self, body: &str) -> (u16, Value) {
+        let submission: Submission = match serde_json::from_str(body) {
+            Ok(submission) => submission,
+            Err(err) => return error(400, &format!("bad submission: {}", err)),
+        };
+
+        let name = submission.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN || name.chars().any(char::is_control) {
+            return error(400, &format!("name must be 1 to {} printable characters", MAX_NAME_LEN));
+        }
+
+        let verified = match submission.replay.verify() {
+            Ok(verified) => verified,
+            Err(err) => return error(422, &format!("replay rejected: {}", err)),
+        };
+
+        // ✅ The token is the settings, seed and score: one entry for each, under any name
+        let challenge = submission.replay.challenge.trim().to_string();
+        if self.entries.iter().any(|entry| entry.challenge == challenge) {
+            return error(409, "this run is already on the board");
+        }
+
+        let entry = Entry {
+            id: self.entries.last().map_or(1, |entry| entry.id + 1),
+            name,
+            game: verified.config.game().name().to_string(),
+            seed: verified.seed,
+            score: verified.score,
+            challenge,
+            submitted_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs()),
+        };
+        let rank = self.top(verified.config.game(), None, usize::MAX)
+            .iter()
+            .take_while(|other| other.score > entry.score)
+            .count()
+            + 1;
+
+        if let Err(err) = self.add(entry.clone()) {
+            eprintln!("leaderboard: can't save entry: {}", err);
+            return error(500, "can't save the score");
+        }
+        (201, json!({ "entry": entry, "rank": rank }))
+    }
+}
+
+fn main() {
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut data = DEFAULT_DATA.to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data" => data = args.next().unwrap_or_else(|| exit_with_usage()),
+            "-h" | "--help" => exit_with_usage(),
+            _ => addr = arg,
+        }
+    }
+
+    let mut leaderboard = match Leaderboard::open(&data) {
+        Ok(leaderboard) => leaderboard,
+        Err(err) => {
+            eprintln!("leaderboard: can't open {}: {}", data, err);
+            std::process::exit(1);
+        }
+    };
+    let server = match Server::http(&addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("leaderboard: can't listen on {}: {}", addr, err);
+            std::process::exit(1);
+        }
+    };
+    println!("leaderboard: listening on http://{} ({} scores in {})", addr, leaderboard.entries.len(), data);
+
+    // ✅ One request at a time, so the file stays in order. Playing a replay back
+    // costs its own length plus `END_GRACE` ticks: milliseconds for most runs,
+    // but an hour-long Pac-Man run takes seconds and holds the rest up meanwhile
+    for request in server.incoming_requests() {
+        if let Err(err) = handle(&mut leaderboard, request) {
+            eprintln!("leaderboard: {}", err);
+        }
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("usage: leaderboard [ADDR] [--data FILE]   (default {}, {})", DEFAULT_ADDR, DEFAULT_DATA);
+    std::process::exit(2);
+}
+
+fn handle(leaderboard: &mut Leaderboard, mut request: Request) -> io::Result<()> {
+    let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    let path: Vec<String> = path.split('/').filter(|part| !part.is_empty()).map(str::to_string).collect();
+    let limit = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("limit="))
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_LIMIT);
+
+    let (status, body) = match (request.method(), path.as_slice()) {
+        (Method::Options, _) => (204, Value::Null), // ✅ CORS preflight
+        (Method::Post, [scores]) if scores == "scores" => {
+            let mut body = String::new();
+            let read = request.as_reader().take(MAX_BODY + 1).read_to_string(&mut body);
+            match read {
+                Ok(_) if body.len() as u64 > MAX_BODY => error(413, "submission too large"),
+                Ok(_) => leaderboard.submit(&body),
+                Err(_) => error(400, "submission isn't UTF-8"),
+            }
+        }
+        (Method::Get, [boards, game, rest @ ..]) if boards == "boards" => match (GameKind::from_name(game), rest) {
+            (None, _) => error(404, "no such game"),
+            (Some(game), []) => (200, json!(leaderboard.top(game, None, limit))),
+            (Some(game), [seeds, seed]) if seeds == "seeds" => match seed.parse() {
+                Ok(seed) => (200, json!(leaderboard.top(game, Some(seed), limit))),
+                Err(_) => error(400, "seed must be a number"),
+            },
+            _ => error(404, "not found"),
+        },
+        _ => error(404, "not found"),
+    };
+
+    let body = if body.is_null() { String::new() } else { body.to_string() };
+    let mut response = Response::from_string(body).with_status_code(status);
+    for (name, value) in [
+        ("Content-Type", "application/json"),
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+        ("Access-Control-Allow-Headers", "Content-Type"),
+    ] {
+        response.add_header(Header::from_bytes(name, value).expect("valid header"));
+    }
+    request.respond(response)
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
@@ -0,0 +1,287 @@
+//! Relay for online snake and remote spectators. Pairs browsers up in rooms,
+//! hands each match a seed, forwards every input to the other player and
+//! compares the state hashes both sides report. Spectator channels pass a
+//! publisher's frames on to its watchers.
+//!
+//! ```text
+//! cargo run --bin relay -- [ADDR] [--input-delay TICKS]
+//! ```
+//!
+//! then open `index.html?game=snake&online=ws://localhost:9001` in two tabs, or
+//! `?spectate=demo&relay=ws://localhost:9001` and `?watch=demo&relay=...`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use tungstenite::error::ProtocolError;
+use tungstenite::{Error, Message};
+use wasm_games::{ClientMessage, ServerMessage, DEFAULT_INPUT_DELAY, ROOM_SIZE};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:9001";
+/// How long a connection waits for a frame before passing on queued messages.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+#[derive(Default)]
+struct Room {
+    players: [Option<Sender<ServerMessage>>; ROOM_SIZE],
+    match_id: u32,
+    /// Hashes reported for ticks not every player has reported yet.
+    hashes: BTreeMap<u32, [Option<u64>; ROOM_SIZE]>,
+}
+
+impl Room {
+    fn is_full(&self) -> bool {
+        self.players.iter().all(Option::is_some)
+    }
+
+    fn broadcast(&self, message: &ServerMessage) {
+        for player in self.players.iter().flatten() {
+            player.send(message.clone()).ok();
+        }
+    }
+
+    fn send_others(&self, sender: usize, message: &ServerMessage) {
+        for (i, player) in self.players.iter().enumerate() {
+            if let (Some(player), true) = (player, i != sender) {
+                player.send(message.clone()).ok();
+            }
+        }
+    }
+}
+
+/// One publisher's frames and everybody watching them, by connection id.
+#[derive(Default)]
+struct Channel {
+    publisher: Option<(u64, Sender<ServerMessage>)>,
+    watchers: HashMap<u64, Sender<ServerMessage>>,
+}
+
+#[derive(Default)]
+struct Relay {
+    rooms: HashMap<String, Room>,
+    channels: HashMap<String, Channel>,
+}
+
+type Shared = Arc<Mutex<Relay>>;
+
+/// What a connection has joined.
+enum Seat {
+    Player { room: String, player: usize },
+    Publisher { channel: String },
+    Watcher { channel: String },
+}
+
+struct Connection {
+    id: u64,
+    outbox: Sender<ServerMessage>,
+    seat: Option<Seat>,
+}
+
+fn main() {
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut input_delay = DEFAULT_INPUT_DELAY;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-delay" => {
+                input_delay = match args.next().and_then(|value| value.parse().ok()) {
+                    Some(delay) => delay,
+                    None => exit_with_usage(),
+                }
+            }
+            "-h" | "--help" => exit_with_usage(),
+            _ => addr = arg,
+        }
+    }
+
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("relay: can't listen on {}: {}", addr, err);
+            std::process::exit(1);
+        }
+    };
+    println!("relay: listening on ws://{} (input delay {} ticks)", addr, input_delay);
+
+    let relay = Shared::default();
+    for (id, stream) in listener.incoming().flatten().enumerate() {
+        let relay = relay.clone();
+        thread::spawn(move || {
+            let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+            if let Err(err) = serve(id as u64, stream, &relay, input_delay) {
+                eprintln!("relay: {}: {}", peer, err);
+            }
+        });
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("usage: relay [ADDR] [--input-delay TICKS]   (default {})", DEFAULT_ADDR);
+    std::process::exit(2);
+}
+
+/// Runs one WebSocket connection until it closes, then frees its seat.
+fn serve(id: u64, stream: TcpStream, relay: &Shared, input_delay: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let mut socket = tungstenite::accept(stream).map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => Error::ConnectionClosed,
+    })?;
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+
+    let (outbox, queued) = mpsc::channel();
+    let mut connection = Connection { id, outbox, seat: None };
+    let result = 'serve: loop {
+        for message in queued.try_iter() {
+            let text = serde_json::to_string(&message).expect("server messages serialize");
+            if let Err(err) = socket.send(Message::text(text)) {
+                break 'serve Err(err.into());
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(message) => handle(message, &mut connection, relay, input_delay),
+                Err(err) => eprintln!("relay: bad message: {}", err),
+            },
+            Ok(Message::Close(_)) => break Ok(()),
+            Ok(_) => {}
+            Err(Error::Io(err)) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(Error::ConnectionClosed | Error::Protocol(ProtocolError::ResetWithoutClosingHandshake)) => break Ok(()),
+            Err(err) => break Err(err.into()),
+        }
+    };
+
+    leave(connection, relay);
+    result
+}
+
+fn handle(message: ClientMessage, connection: &mut Connection, relay: &Shared, input_delay: u32) {
+    let mut relay = relay.lock().unwrap();
+
+    match message {
+        ClientMessage::Join { room: name } => {
+            if connection.seat.is_some() {
+                return;
+            }
+            let room = relay.rooms.entry(name.clone()).or_default();
+            let Some(player) = room.players.iter().position(Option::is_none) else {
+                connection.outbox.send(ServerMessage::RoomFull).ok();
+                return;
+            };
+            room.players[player] = Some(connection.outbox.clone());
+            connection.seat = Some(Seat::Player { room: name.clone(), player });
+            println!("relay: player {} joined room {:?}", player + 1, name);
+
+            if !room.is_full() {
+                connection.outbox.send(ServerMessage::Waiting { player }).ok();
+                return;
+            }
+
+            // ✅ Everybody's here: a fresh match for the whole room
+            room.match_id += 1;
+            room.hashes.clear();
+            let seed = rand::random();
+            for (player, sender) in room.players.iter().enumerate() {
+                if let Some(sender) = sender {
+                    let start = ServerMessage::Start { player, match_id: room.match_id, seed, input_delay };
+                    sender.send(start).ok();
+                }
+            }
+        }
+        ClientMessage::Input { match_id, tick, direction } => {
+            let Some((room, player)) = seated_in(connection, &mut relay, match_id) else { return };
+            room.send_others(player, &ServerMessage::Input { player, tick, direction });
+        }
+        ClientMessage::Hash { match_id, tick, hash } => {
+            let Some((room, player)) = seated_in(connection, &mut relay, match_id) else { return };
+            let hashes = room.hashes.entry(tick).or_default();
+            hashes[player] = Some(hash);
+            if hashes.iter().any(Option::is_none) {
+                return;
+            }
+
+            let hashes = room.hashes.remove(&tick).unwrap_or_default();
+            if hashes.iter().any(|&other| other != hashes[0]) {
+                eprintln!("relay: desync at tick {}: {:?}", tick, hashes);
+                room.broadcast(&ServerMessage::Desync { tick });
+            }
+        }
+        ClientMessage::Publish { channel: name } => {
+            if connection.seat.is_some() {
+                return;
+            }
+            let channel = relay.channels.entry(name.clone()).or_default();
+            channel.publisher = Some((connection.id, connection.outbox.clone()));
+            connection.seat = Some(Seat::Publisher { channel: name.clone() });
+            println!("relay: publishing on channel {:?}", name);
+        }
+        ClientMessage::Watch { channel: name } => {
+            if connection.seat.is_some() {
+                return;
+            }
+            let channel = relay.channels.entry(name.clone()).or_default();
+            channel.watchers.insert(connection.id, connection.outbox.clone());
+            connection.seat = Some(Seat::Watcher { channel: name });
+        }
+        ClientMessage::Spectate { message } => {
+            let (name, publishing) = match &connection.seat {
+                Some(Seat::Publisher { channel }) => (channel, true),
+                Some(Seat::Watcher { channel }) => (channel, false),
+                _ => return,
+            };
+            let Some(channel) = relay.channels.get(name) else { return };
+            let message = ServerMessage::Spectate { message };
+            if publishing {
+                for watcher in channel.watchers.values() {
+                    watcher.send(message.clone()).ok();
+                }
+            } else if let Some((_, publisher)) = &channel.publisher {
+                publisher.send(message).ok();
+            }
+        }
+    }
+}
+
+/// The sender's room, if it's in one that is still playing match `match_id`.
+fn seated_in<'a>(connection: &Connection, relay: &'a mut Relay, match_id: u32) -> Option<(&'a mut Room, usize)> {
+    let Some(Seat::Player { room, player }) = &connection.seat else { return None };
+    let room = relay.rooms.get_mut(room)?;
+    (room.is_full() && room.match_id == match_id).then_some((room, *player))
+}
+
+fn leave(connection: Connection, relay: &Shared) {
+    let Some(seat) = connection.seat else { return };
+    let mut relay = relay.lock().unwrap();
+
+    match seat {
+        Seat::Player { room: name, player } => {
+            let Some(room) = relay.rooms.get_mut(&name) else { return };
+            room.players[player] = None;
+            room.hashes.clear();
+            println!("relay: player {} left room {:?}", player + 1, name);
+
+            if room.players.iter().all(Option::is_none) {
+                relay.rooms.remove(&name);
+            } else {
+                room.broadcast(&ServerMessage::PeerLeft { player });
+            }
+        }
+        Seat::Publisher { channel: name } | Seat::Watcher { channel: name } => {
+            let Some(channel) = relay.channels.get_mut(&name) else { return };
+            if matches!(channel.publisher, Some((id, _)) if id == connection.id) {
+                channel.publisher = None;
+                println!("relay: stopped publishing on channel {:?}", name);
+            }
+            channel.watchers.remove(&connection.id);
+
+            if channel.publisher.is_none() && channel.watchers.is_empty() {
+                relay.channels.remove(&name);
+            }
+        }
+    }
+}
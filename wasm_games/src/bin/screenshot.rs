@@ -0,0 +1,82 @@
+//! Saves one frame of a seeded game as a PNG, drawn by the software
+//! rasterizer (`raster.rs`), for bug reports and quick looks without a
+//! browser. Options are the `index.html` query parameters as `key=value`
+//! arguments; a bot plays for `--ticks` first:
+//!
+//! ```text
+//! cargo run --bin screenshot -- game=pacman seed=42 maze=corridors --out maze.png
+//! cargo run --bin screenshot -- game=flappy seed=7 --bot random --ticks 90
+//! cargo run --bin screenshot -- game=snake theme=gameboy --ticks 300
+//! ```
+
+use std::collections::HashMap;
+
+use wasm_games::raster::Image;
+use wasm_games::{agent, new_sim, GameConfig, AGENTS, TICK};
+
+const DEFAULT_BOT: &str = "greedy";
+const DEFAULT_OUT: &str = "frame.png";
+
+fn main() {
+    let mut params = HashMap::new();
+    let (mut bot, mut ticks, mut out) = (DEFAULT_BOT.to_string(), 0, DEFAULT_OUT.to_string());
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some((key, value)) = arg.split_once('=').filter(|_| !arg.starts_with("--")) {
+            params.insert(key.to_string(), value.to_string());
+            continue;
+        }
+
+        let value = args.next().unwrap_or_else(|| exit_with_usage());
+        match arg.as_str() {
+            "--bot" => bot = value,
+            "--ticks" => {
+                ticks = value.parse().unwrap_or_else(|_| {
+                    eprintln!("screenshot: --ticks expects a number, got \"{}\"", value);
+                    std::process::exit(2);
+                })
+            }
+            "--out" => out = value,
+            _ => exit_with_usage(),
+        }
+    }
+
+    let config = match GameConfig::from_lookup(|key| params.get(key).cloned()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("screenshot: {}", err);
+            std::process::exit(2);
+        }
+    };
+    if config.online().is_some() || config.watch().is_some() {
+        eprintln!("screenshot: online games and spectating need the browser");
+        std::process::exit(2);
+    }
+    let Some(mut bot) = agent(&bot, config.seed().unwrap_or(0)) else {
+        eprintln!("screenshot: unknown bot \"{}\": expected one of {}", bot, AGENTS.join(", "));
+        std::process::exit(2);
+    };
+
+    let mut sim = new_sim(&config);
+    for _ in 0..ticks {
+        if let Some(command) = bot.act(&sim.view()) {
+            sim.command(command);
+        }
+        sim.update(TICK);
+        sim.take_events();
+    }
+
+    if let Err(err) = Image::render(&sim.view(), config.style()).save_png(&out) {
+        eprintln!("screenshot: {}: {}", out, err);
+        std::process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!(
+        "usage: screenshot [game=...] [seed=N] [OPTION=VALUE ...] [--bot {}] [--ticks N] [--out FILE]",
+        AGENTS.join("|")
+    );
+    std::process::exit(2);
+}
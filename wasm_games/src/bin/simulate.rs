@@ -0,0 +1,337 @@
+//! Plays thousands of seeded games with a bot and reports how they went, for
+//! balancing with data instead of feel. Options are the `index.html` query
+//! parameters as `key=value` arguments, plus flags for the batch and for
+//! overriding tuning constants:
+//!
+//! ```text
+//! cargo run --release --bin simulate -- game=flappy --bot greedy --runs 5000 --pipe-gap 140
+//! cargo run --release --bin simulate -- game=pacman --ghost-randomness 20 --format csv > runs.csv
+//! ```
+//!
+//! JSON (the default) is a summary plus every run; CSV is one row per run.
+//! Run `i` plays seed `--seed + i`, so batches are reproducible.
+
+use std::collections::{BTreeMap, HashMap};
+use std::thread;
+
+use serde::Serialize;
+use serde_json::json;
+use wasm_games::{agent, new_sim, GameConfig, GameEvent, GameKind, Tuning, View, AGENTS, TICK};
+
+const DEFAULT_RUNS: u32 = 1000;
+const DEFAULT_BOT: &str = "greedy";
+/// Runs still going after this long stop with cause `timeout`.
+const DEFAULT_MAX_SECONDS: f64 = 600.0;
+
+struct Options {
+    params: HashMap<String, String>,
+    bot: String,
+    runs: u32,
+    first_seed: u64,
+    csv: bool,
+    max_ticks: u32,
+    threads: usize,
+    tuning: Tuning,
+}
+
+/// How one game went.
+#[derive(Serialize)]
+struct Run {
+    seed: u64,
+    score: u32,
+    /// Ticks from the first command to game over.
+    ticks: u32,
+    cause: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pacman: Option<PacmanRun>,
+}
+
+#[derive(Clone, Copy, Default, Serialize)]
+struct PacmanRun {
+    mazes_cleared: u32,
+    ghosts_eaten: u32,
+    /// The first maze of the run.
+    maze: MazeStats,
+}
+
+#[derive(Clone, Copy, Default, Serialize)]
+struct MazeStats {
+    walls: u32,
+    pellets: u32,
+    power_pellets: u32,
+    /// Open tiles with only one way out.
+    dead_ends: u32,
+}
+
+fn main() {
+    let options = parse_args();
+
+    let base = match config(&options, options.first_seed) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("simulate: {}", err);
+            std::process::exit(2);
+        }
+    };
+    if base.players() != 1 || base.online().is_some() || base.watch().is_some() {
+        eprintln!("simulate: bots play solo, offline games only");
+        std::process::exit(2);
+    }
+    if agent(&options.bot, 0).is_none() {
+        eprintln!("simulate: unknown bot \"{}\": expected one of {}", options.bot, AGENTS.join(", "));
+        std::process::exit(2);
+    }
+
+    // ✅ Runs are independent; deal them out round-robin and put them back in order
+    let mut runs: Vec<(u32, Run)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..options.threads)
+            .map(|worker| {
+                let options = &options;
+                scope.spawn(move || {
+                    (worker as u32..options.runs)
+                        .step_by(options.threads)
+                        .map(|i| (i, play(options, options.first_seed + i as u64)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().expect("simulation thread panicked")).collect()
+    });
+    runs.sort_by_key(|&(i, _)| i);
+    let runs: Vec<Run> = runs.into_iter().map(|(_, run)| run).collect();
+
+    let summary = summarize(base.game(), &options, &runs);
+    eprintln!(
+        "simulate: {} {} runs with {}: mean score {:.2}, mean lifetime {:.1}s",
+        runs.len(),
+        base.game().name(),
+        options.bot,
+        summary["score"]["mean"].as_f64().unwrap_or(0.0),
+        summary["lifetime_seconds"]["mean"].as_f64().unwrap_or(0.0),
+    );
+
+    if options.csv {
+        print_csv(&runs);
+    } else {
+        let report = json!({ "summary": summary, "runs": runs });
+        println!("{}", serde_json::to_string_pretty(&report).expect("reports serialize"));
+    }
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        params: HashMap::new(),
+        bot: DEFAULT_BOT.to_string(),
+        runs: DEFAULT_RUNS,
+        first_seed: 0,
+        csv: false,
+        max_ticks: (DEFAULT_MAX_SECONDS / TICK) as u32,
+        threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        tuning: Tuning::default(),
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some((key, value)) = arg.split_once('=').filter(|_| !arg.starts_with("--")) {
+            options.params.insert(key.to_string(), value.to_string());
+            continue;
+        }
+
+        let mut value = || args.next().unwrap_or_else(|| exit_with_usage());
+        match arg.as_str() {
+            "--bot" => options.bot = value(),
+            "--runs" => options.runs = number(&arg, &value()),
+            "--seed" => options.first_seed = number(&arg, &value()),
+            "--threads" => options.threads = number::<usize>(&arg, &value()).max(1),
+            "--max-seconds" => options.max_ticks = (number::<f64>(&arg, &value()) / TICK) as u32,
+            "--format" => {
+                options.csv = match value().as_str() {
+                    "csv" => true,
+                    "json" => false,
+                    _ => exit_with_usage(),
+                }
+            }
+            "--gravity" => options.tuning.gravity = Some(number(&arg, &value())),
+            "--pipe-gap" => options.tuning.pipe_gap = Some(number(&arg, &value())),
+            "--snake-move-interval" => options.tuning.snake_move_interval = Some(number(&arg, &value())),
+            "--ghost-randomness" => options.tuning.ghost_randomness = Some(number::<u32>(&arg, &value()).min(100)),
+            _ => exit_with_usage(),
+        }
+    }
+    options
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| {
+        eprintln!("simulate: {} expects a number, got \"{}\"", flag, value);
+        std::process::exit(2);
+    })
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!(
+        "usage: simulate [game=...] [OPTION=VALUE ...] [--bot {}] [--runs N] [--seed N] [--format json|csv]\n\
+         \x20               [--max-seconds S] [--threads N] [--gravity PX/S²] [--pipe-gap X]\n\
+         \x20               [--snake-move-interval S] [--ghost-randomness PERCENT]",
+        AGENTS.join("|")
+    );
+    std::process::exit(2);
+}
+
+fn config(options: &Options, seed: u64) -> Result<GameConfig, wasm_games::ConfigError> {
+    let seed = seed.to_string();
+    let mut config = GameConfig::from_lookup(|key| match key {
+        "seed" => Some(seed.clone()),
+        _ => options.params.get(key).cloned(),
+    })?;
+    config.set_tuning(options.tuning);
+    Ok(config)
+}
+
+fn play(options: &Options, seed: u64) -> Run {
+    let config = config(options, seed).expect("validated in main");
+    let mut sim = new_sim(&config);
+    let mut bot = agent(&options.bot, seed).expect("validated in main");
+
+    let mut pacman = match sim.view() {
+        View::Pacman(view) => Some(PacmanRun { maze: maze_stats(&view.rows), ..Default::default() }),
+        _ => None,
+    };
+    let (mut started, mut ticks, mut cause) = (false, 0, None);
+
+    while ticks < options.max_ticks {
+        let mut events = Vec::new();
+        if let Some(command) = bot.act(&sim.view()) {
+            sim.command(command);
+            events.extend(sim.take_events());
+        }
+        sim.update(TICK);
+        events.extend(sim.take_events());
+        if started {
+            ticks += 1;
+        }
+
+        for event in events {
+            match event {
+                GameEvent::Started => started = true,
+                GameEvent::Died { player: 0, cause: died } => cause = Some(died.name().to_string()),
+                GameEvent::MazeCleared { .. } => pacman.iter_mut().for_each(|run| run.mazes_cleared += 1),
+                GameEvent::GhostEaten { .. } => pacman.iter_mut().for_each(|run| run.ghosts_eaten += 1),
+                GameEvent::GameOver { score } => {
+                    return Run { seed, score, ticks, cause: cause.unwrap_or_else(|| "other".to_string()), pacman };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Run { seed, score: sim.scores()[0], ticks, cause: "timeout".to_string(), pacman }
+}
+
+fn maze_stats(rows: &[String]) -> MazeStats {
+    let grid: Vec<&[u8]> = rows.iter().map(|row| row.as_bytes()).collect();
+    let is_open = |x: usize, y: usize| grid.get(y).and_then(|row| row.get(x)).is_some_and(|&tile| tile != b'#');
+
+    let mut stats = MazeStats::default();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            match tile {
+                b'#' => stats.walls += 1,
+                b'.' => stats.pellets += 1,
+                b'o' => stats.power_pellets += 1,
+                _ => {}
+            }
+            if tile != b'#' {
+                let exits = [(x + 1, y), (x, y + 1), (x.wrapping_sub(1), y), (x, y.wrapping_sub(1))]
+                    .iter()
+                    .filter(|&&(x, y)| is_open(x, y))
+                    .count();
+                if exits == 1 {
+                    stats.dead_ends += 1;
+                }
+            }
+        }
+    }
+    stats
+}
+
+fn summarize(game: GameKind, options: &Options, runs: &[Run]) -> serde_json::Value {
+    let scores: Vec<f64> = runs.iter().map(|run| run.score as f64).collect();
+    let lifetimes: Vec<f64> = runs.iter().map(|run| run.ticks as f64 * TICK).collect();
+
+    let mut histogram = BTreeMap::new();
+    let mut causes = BTreeMap::new();
+    for run in runs {
+        *histogram.entry(run.score).or_insert(0) += 1;
+        *causes.entry(run.cause.as_str()).or_insert(0) += 1;
+    }
+
+    let mut summary = json!({
+        "game": game.name(),
+        "bot": options.bot,
+        "runs": runs.len(),
+        "first_seed": options.first_seed,
+        "params": options.params,
+        "tuning": {
+            "gravity": options.tuning.gravity,
+            "pipe_gap": options.tuning.pipe_gap,
+            "snake_move_interval": options.tuning.snake_move_interval,
+            "ghost_randomness": options.tuning.ghost_randomness,
+        },
+        "score": distribution(&scores),
+        "score_histogram": histogram,
+        "lifetime_seconds": distribution(&lifetimes),
+        "causes": causes,
+    });
+
+    let pacman: Vec<PacmanRun> = runs.iter().filter_map(|run| run.pacman).collect();
+    if !pacman.is_empty() {
+        let mean = |value: fn(&PacmanRun) -> u32| {
+            pacman.iter().map(|run| value(run) as f64).sum::<f64>() / pacman.len() as f64
+        };
+        summary["maze"] = json!({
+            "mean_mazes_cleared": mean(|run| run.mazes_cleared),
+            "mean_ghosts_eaten": mean(|run| run.ghosts_eaten),
+            "mean_walls": mean(|run| run.maze.walls),
+            "mean_pellets": mean(|run| run.maze.pellets),
+            "mean_power_pellets": mean(|run| run.maze.power_pellets),
+            "mean_dead_ends": mean(|run| run.maze.dead_ends),
+        });
+    }
+    summary
+}
+
+fn distribution(values: &[f64]) -> serde_json::Value {
+    if values.is_empty() {
+        return serde_json::Value::Null;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mean = sorted.iter().sum::<f64>() / sorted.len() as f64;
+    let variance = sorted.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / sorted.len() as f64;
+    let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+
+    json!({
+        "mean": mean,
+        "stddev": variance.sqrt(),
+        "min": sorted[0],
+        "p10": percentile(0.1),
+        "median": percentile(0.5),
+        "p90": percentile(0.9),
+        "max": sorted[sorted.len() - 1],
+    })
+}
+
+fn print_csv(runs: &[Run]) {
+    println!("run,seed,score,ticks,seconds,cause,mazes_cleared,ghosts_eaten,maze_walls,maze_pellets,maze_power_pellets,maze_dead_ends");
+    for (i, run) in runs.iter().enumerate() {
+        let pacman = match run.pacman {
+            Some(p) => format!(
+                "{},{},{},{},{},{}",
+                p.mazes_cleared, p.ghosts_eaten, p.maze.walls, p.maze.pellets, p.maze.power_pellets, p.maze.dead_ends
+            ),
+            None => ",,,,,".to_string(),
+        };
+        println!("{},{},{},{},{:.3},{},{}", i, run.seed, run.score, run.ticks, run.ticks as f64 * TICK, run.cause, pacman);
+    }
+}
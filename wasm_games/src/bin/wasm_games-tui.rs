@@ -0,0 +1,126 @@
+//! Plays the games in a terminal, with the same rules as the site, for quick
+//! checks over SSH without building the wasm package. Options are the
+//! `index.html` query parameters as `key=value` arguments:
+//!
+//! ```text
+//! cargo run --bin wasm_games-tui -- game=pacman seed=42 difficulty=hard
+//! ```
+//!
+//! Arrows (Space flaps) for player 1, WASD for player 2 with `players=2`;
+//! `q` or Esc quits.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, terminal};
+use wasm_games::{new_sim, term, GameConfig, View, TICK};
+
+/// Redraw at most this often; every tick would flood a slow SSH link.
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+/// Most ticks run back to back after a stall before the clock skips ahead.
+const MAX_CATCH_UP: u32 = 6;
+
+/// Puts the terminal back however the game ends, panics included.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide, terminal::Clear(terminal::ClearType::All))?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen).ok();
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+fn main() {
+    let mut options = HashMap::new();
+    for arg in std::env::args().skip(1) {
+        match arg.split_once('=') {
+            Some((key, value)) => {
+                options.insert(key.to_string(), value.to_string());
+            }
+            None => exit_with_usage(),
+        }
+    }
+
+    let config = match GameConfig::from_lookup(|key| options.get(key).cloned()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("wasm_games-tui: {}", err);
+            std::process::exit(2);
+        }
+    };
+    if config.online().is_some() || config.spectate().is_some() || config.watch().is_some() {
+        eprintln!("wasm_games-tui: online play and spectating need the browser");
+        std::process::exit(2);
+    }
+
+    if let Err(err) = play(&config) {
+        eprintln!("wasm_games-tui: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("usage: wasm_games-tui [game=flappy|snake|pacman] [seed=N] [players=2] [OPTION=VALUE ...]");
+    std::process::exit(2);
+}
+
+fn play(config: &GameConfig) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut stdout = io::stdout();
+
+    let mut sim = new_sim(config);
+    let style = config.style();
+    let tick = Duration::from_secs_f64(TICK);
+    let mut next_tick = Instant::now();
+    let mut next_frame = Instant::now();
+    let mut shown: Option<View> = None;
+
+    loop {
+        while event::poll(next_tick.saturating_duration_since(Instant::now()))? {
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) || ctrl_c {
+                return Ok(());
+            }
+            if let Some(command) = term::command(config.game(), config.players(), key.code) {
+                sim.command(command);
+            }
+        }
+
+        // ✅ Fixed steps like the browser loop, catching up at most a few after a stall
+        let mut steps = 0;
+        while Instant::now() >= next_tick && steps < MAX_CATCH_UP {
+            sim.update(TICK);
+            sim.take_events(); // ✅ Nothing to unlock here
+            next_tick += tick;
+            steps += 1;
+        }
+        if steps == MAX_CATCH_UP {
+            next_tick = Instant::now() + tick;
+        }
+
+        if Instant::now() >= next_frame {
+            let view = sim.view();
+            if shown.as_ref() != Some(&view) {
+                queue!(stdout, cursor::MoveTo(0, 0))?;
+                stdout.write_all(term::render(&view, &style).as_bytes())?;
+                stdout.flush()?;
+                shown = Some(view);
+            }
+            next_frame = Instant::now() + FRAME_INTERVAL;
+        }
+    }
+}
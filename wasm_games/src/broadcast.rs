@@ -0,0 +1,233 @@
+//! Carries `spectate.rs` messages between tabs over a `BroadcastChannel`, and
+//! to viewers elsewhere through the relay when `relay=` is set.
+//!
+//! `?game=pacman&spectate=demo` publishes; `?watch=demo` in another tab shows
+//! it. Add `&relay=ws://localhost:9001` on both sides to watch from another
+//! machine.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{BroadcastChannel, CanvasRenderingContext2d, Event, HtmlCanvasElement, MessageEvent, WebSocket};
+
+use crate::config::SpectateConfig;
+use crate::net::{ClientMessage, ServerMessage};
+use crate::spectate::{Publisher, SpectateMessage, Viewer};
+use crate::locale::fill;
+use crate::theme::Style;
+use crate::view::View;
+
+/// Updates between a lost viewer's requests for a keyframe.
+const HELLO_INTERVAL: u32 = 30;
+
+type MessageListener = Closure<dyn FnMut(MessageEvent)>;
+type EventListener = Closure<dyn FnMut(Event)>;
+
+/// Both directions of one spectator channel.
+struct Link {
+    channel: Option<BroadcastChannel>,
+    socket: Option<WebSocket>,
+    inbox: Rc<RefCell<VecDeque<SpectateMessage>>>,
+    _listeners: (Option<MessageListener>, Option<(EventListener, MessageListener)>),
+}
+
+impl Link {
+    /// Publishers go out on both transports; viewers listen on the relay if
+    /// there is one, otherwise on the BroadcastChannel.
+    fn open(config: &SpectateConfig, publishing: bool) -> Result<Link, JsValue> {
+        let inbox = Rc::new(RefCell::new(VecDeque::new()));
+
+        let (socket, socket_listeners) = match &config.relay {
+            Some(relay) => {
+                let socket = WebSocket::new(relay)?;
+
+                let open_socket = socket.clone();
+                let channel = config.channel.clone();
+                let on_open = Closure::<dyn FnMut(Event)>::new(move |_event: Event| {
+                    if publishing {
+                        send(&open_socket, &ClientMessage::Publish { channel: channel.clone() });
+                    } else {
+                        send(&open_socket, &ClientMessage::Watch { channel: channel.clone() });
+                        send(&open_socket, &ClientMessage::Spectate { message: SpectateMessage::Hello });
+                    }
+                });
+
+                let socket_inbox = inbox.clone();
+                let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                    let Some(text) = event.data().as_string() else { return };
+                    if let Ok(ServerMessage::Spectate { message }) = serde_json::from_str(&text) {
+                        socket_inbox.borrow_mut().push_back(message);
+                    }
+                });
+
+                socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+                socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+                (Some(socket), Some((on_open, on_message)))
+            }
+            None => (None, None),
+        };
+
+        let (channel, channel_listener) = if publishing || socket.is_none() {
+            let channel = BroadcastChannel::new(&format!("wasm_games.spectate.{}", config.channel))?;
+            let channel_inbox = inbox.clone();
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                let Some(text) = event.data().as_string() else { return };
+                if let Ok(message) = serde_json::from_str(&text) {
+                    channel_inbox.borrow_mut().push_back(message);
+                }
+            });
+            channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            (Some(channel), Some(on_message))
+        } else {
+            (None, None)
+        };
+
+        let link = Link {
+            channel,
+            socket,
+            inbox,
+            _listeners: (channel_listener, socket_listeners),
+        };
+        if !publishing {
+            link.send(&SpectateMessage::Hello);
+        }
+        Ok(link)
+    }
+
+    fn send(&self, message: &SpectateMessage) {
+        if let Some(channel) = &self.channel {
+            let text = serde_json::to_string(message).expect("spectate messages serialize");
+            channel.post_message(&text.into()).ok();
+        }
+        if let Some(socket) = &self.socket {
+            send(socket, &ClientMessage::Spectate { message: message.clone() });
+        }
+    }
+
+    fn receive(&self) -> Option<SpectateMessage> {
+        self.inbox.borrow_mut().pop_front()
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        if let Some(channel) = &self.channel {
+            channel.set_onmessage(None);
+            channel.close();
+        }
+        if let Some(socket) = &self.socket {
+            socket.set_onopen(None);
+            socket.set_onmessage(None);
+            socket.close().ok();
+        }
+    }
+}
+
+fn send(socket: &WebSocket, message: &ClientMessage) {
+    if socket.ready_state() != WebSocket::OPEN {
+        return;
+    }
+    let text = serde_json::to_string(message).expect("client messages serialize");
+    socket.send_with_str(&text).ok();
+}
+
+/// Publishes a running game's frames to its spectators.
+pub struct Broadcaster {
+    link: Link,
+    publisher: Publisher,
+}
+
+impl Broadcaster {
+    pub fn open(config: &SpectateConfig) -> Result<Broadcaster, JsValue> {
+        Ok(Broadcaster {
+            link: Link::open(config, true)?,
+            publisher: Publisher::new(),
+        })
+    }
+
+    pub fn publish(&mut self, view: &View) {
+        while let Some(message) = self.link.receive() {
+            if message == SpectateMessage::Hello {
+                self.publisher.request_keyframe(); // ✅ Somebody new is watching
+            }
+        }
+
+        if let Some(message) = self.publisher.publish(view) {
+            self.link.send(&message);
+        }
+    }
+}
+
+/// Shows a game published somewhere else; takes no input.
+pub struct Spectator {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    channel: String,
+    link: Link,
+    viewer: Viewer,
+    hello_in: u32,
+    style: Style,
+}
+
+impl Spectator {
+    pub fn new(config: &SpectateConfig, style: Style, canvas: HtmlCanvasElement) -> Result<Spectator, JsValue> {
+        let context = canvas
+            .get_context("2d")?
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()?;
+
+        Ok(Spectator {
+            canvas,
+            context,
+            channel: config.channel.clone(),
+            link: Link::open(config, false)?,
+            viewer: Viewer::new(),
+            hello_in: HELLO_INTERVAL,
+            style,
+        })
+    }
+
+    pub fn update(&mut self) {
+        while let Some(message) = self.link.receive() {
+            self.viewer.receive(message);
+        }
+
+        // ✅ Keep asking for a keyframe until one arrives
+        if !self.viewer.in_sync() {
+            self.hello_in -= 1;
+            if self.hello_in == 0 {
+                self.link.send(&SpectateMessage::Hello);
+                self.hello_in = HELLO_INTERVAL;
+            }
+        }
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    pub fn render(&self) {
+        let palette = self.style.palette();
+        let Some(view) = self.viewer.view() else {
+            let (width, height) = (self.canvas.width() as f64, self.canvas.height() as f64);
+            self.context.set_fill_style_str(palette.background);
+            self.context.fill_rect(0.0, 0.0, width, height);
+            self.context.set_fill_style_str(palette.text);
+            self.context.set_font("16px monospace");
+            self.context.set_text_align("center");
+            self.context
+                .fill_text(&fill(self.style.strings().waiting_for_channel, &[&self.channel]), width / 2.0, height / 2.0)
+                .unwrap();
+            return;
+        };
+
+        let (width, height) = view.canvas_size();
+        if self.canvas.width() != width || self.canvas.height() != height {
+            self.canvas.set_width(width);
+            self.canvas.set_height(height);
+        }
+        view.draw(&self.context, &self.style);
+    }
+}
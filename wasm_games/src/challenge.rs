@@ -0,0 +1,98 @@
+//! Shareable challenge links: the game, its settings, the seed of a finished
+//! run and the score it reached, packed into a short URL-safe token
+//! (`?challenge=AQEBAAAAZAAA...`). Opening the link replays the same seed, so
+//! the maze, pipes and food come out exactly as they did for the sharer.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::config::{ConfigError, Difficulty, GameConfig, MazeKind, Mode};
+use crate::game::GameKind;
+
+const VERSION: u8 = 1;
+const TOKEN_LEN: usize = 21;
+
+/// Encodes `config` with the seed and score of a finished run.
+pub fn encode(config: &GameConfig, seed: u64, score: u32) -> String {
+    let (board_width, board_height) = config.board.unwrap_or((0, 0));
+
+    let mut bytes = Vec::with_capacity(TOKEN_LEN);
+    bytes.push(VERSION);
+    bytes.push(match config.game {
+        GameKind::Flappy => 0,
+        GameKind::Snake => 1,
+        GameKind::Pacman => 2,
+    });
+    bytes.push(match config.difficulty {
+        Difficulty::Easy => 0,
+        Difficulty::Normal => 1,
+        Difficulty::Hard => 2,
+    });
+    bytes.push(match config.mode {
+        Mode::Classic => 0,
+        Mode::Wrap => 1,
+    });
+    bytes.push(match config.maze {
+        MazeKind::Prim => 0,
+        MazeKind::Corridors => 1,
+        MazeKind::Open => 2,
+    });
+    bytes.extend_from_slice(&((config.speed * 100.0).round() as u16).to_le_bytes());
+    bytes.push(board_width as u8);
+    bytes.push(board_height as u8);
+    bytes.extend_from_slice(&seed.to_le_bytes());
+    bytes.extend_from_slice(&score.to_le_bytes());
+
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Decodes a token back into the settings to play (seed included) and the score to beat.
+pub fn decode(token: &str) -> Result<(GameConfig, u32), ConfigError> {
+    let invalid = || ConfigError::Invalid {
+        param: "challenge",
+        value: token.to_string(),
+        expected: "a link made with \"share challenge\"",
+    };
+
+    let bytes = URL_SAFE_NO_PAD.decode(token.trim()).map_err(|_| invalid())?;
+    if bytes.len() != TOKEN_LEN || bytes[0] != VERSION {
+        return Err(invalid());
+    }
+
+    let game = match bytes[1] {
+        0 => GameKind::Flappy,
+        1 => GameKind::Snake,
+        2 => GameKind::Pacman,
+        _ => return Err(invalid()),
+    };
+
+    let mut config = GameConfig::new(game);
+    config.difficulty = match bytes[2] {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Normal,
+        2 => Difficulty::Hard,
+        _ => return Err(invalid()),
+    };
+    config.mode = match bytes[3] {
+        0 => Mode::Classic,
+        1 => Mode::Wrap,
+        _ => return Err(invalid()),
+    };
+    config.maze = match bytes[4] {
+        0 => MazeKind::Prim,
+        1 => MazeKind::Corridors,
+        2 => MazeKind::Open,
+        _ => return Err(invalid()),
+    };
+    config.speed = u16::from_le_bytes([bytes[5], bytes[6]]) as f64 / 100.0;
+    if bytes[7] != 0 {
+        config.board = Some((bytes[7] as u32, bytes[8] as u32));
+    }
+    config.seed = Some(u64::from_le_bytes(bytes[9..17].try_into().unwrap()));
+    let score = u32::from_le_bytes(bytes[17..21].try_into().unwrap());
+
+    // ✅ Tokens are user input: hold them to the same limits as the query string
+    config.validate().map_err(|_| invalid())?;
+
+    Ok((config, score))
+}
@@ -0,0 +1,732 @@
+//! Game configuration from the query string (`?game=snake&speed=1.5&board=30x20`)
+//! or from `<wasm-game>` attributes, validated into per-game settings. A
+//! `challenge` token (see `challenge.rs`) stands in for all of the other options.
+
+use std::fmt;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use web_sys::{window, UrlSearchParams};
+
+use crate::challenge;
+use crate::game::GameKind;
+use crate::locale::Language;
+use crate::net;
+use crate::theme::{Style, Theme};
+use crate::{flappy, pacman, snake};
+
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 4.0;
+const MIN_BOARD: u32 = 8;
+const MAX_BOARD: u32 = 60;
+const DEFAULT_ROOM: &str = "lobby";
+const DEFAULT_ATTRACT_DELAY: f64 = 30.0;
+const MAX_ATTRACT_DELAY: f64 = 600.0;
+const DEFAULT_SWIPE_THRESHOLD: f64 = 24.0;
+const MIN_SWIPE_THRESHOLD: f64 = 4.0;
+const MAX_SWIPE_THRESHOLD: f64 = 200.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Classic,
+    /// Snake leaves one edge of the board and comes back on the opposite one.
+    Wrap,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MazeKind {
+    /// Prim's maze with most walls knocked out again (the original stage).
+    #[default]
+    Prim,
+    /// Prim's maze left as generated: long single-width corridors.
+    Corridors,
+    /// Only the outer wall.
+    Open,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    Invalid {
+        param: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    Unsupported {
+        param: &'static str,
+        game: GameKind,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Invalid { param, value, expected } => {
+                write!(f, "invalid {} \"{}\": expected {}", param, value, expected)
+            }
+            ConfigError::Unsupported { param, game } => {
+                write!(f, "{} is not supported by {}", param, game.name())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<ConfigError> for JsValue {
+    fn from(err: ConfigError) -> JsValue {
+        JsError::new(&err.to_string()).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct FlappyConfig {
+    pub gravity: f64,
+    /// Upward speed a flap gives, in pixels per second (negative is up).
+    pub jump_strength: f64,
+    pub max_fall_speed: f64,
+    pub pipe_gap: f64,
+    pub pipe_spacing: f64,
+    pub pipe_speed: f64,
+}
+
+impl Default for FlappyConfig {
+    fn default() -> FlappyConfig {
+        GameConfig::new(GameKind::Flappy).flappy()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SnakeConfig {
+    pub width: u32,
+    pub height: u32,
+    pub move_interval: f64,
+    pub wrap: bool,
+}
+
+impl Default for SnakeConfig {
+    fn default() -> SnakeConfig {
+        GameConfig::new(GameKind::Snake).snake()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct PacmanConfig {
+    pub maze: MazeKind,
+    pub pacman_move_interval: f64,
+    pub ghost_move_interval: f64,
+    /// Percent chance per ghost move to wander instead of chasing.
+    pub ghost_randomness: u32,
+}
+
+impl Default for PacmanConfig {
+    fn default() -> PacmanConfig {
+        GameConfig::new(GameKind::Pacman).pacman()
+    }
+}
+
+/// Where to play an online match: a relay (`src/bin/relay.rs`) and a room on it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OnlineConfig {
+    pub server: String,
+    pub room: String,
+}
+
+/// A spectator channel: a BroadcastChannel name, also carried over a relay
+/// when `relay` is set (for viewers outside this browser).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SpectateConfig {
+    pub channel: String,
+    pub relay: Option<String>,
+}
+
+/// Touch controls: how far (in canvas pixels) a finger has to move to count
+/// as a swipe, and whether to draw an on-screen D-pad for the grid games.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct TouchConfig {
+    pub swipe_threshold: f64,
+    pub dpad: bool,
+}
+
+impl Default for TouchConfig {
+    fn default() -> TouchConfig {
+        TouchConfig { swipe_threshold: DEFAULT_SWIPE_THRESHOLD, dpad: false }
+    }
+}
+
+/// Balancing overrides for the values a game would otherwise run with, for
+/// experiments like `src/bin/simulate.rs`. Not settable from the URL and not
+/// carried in challenge tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tuning {
+    pub gravity: Option<f64>,
+    pub pipe_gap: Option<f64>,
+    pub snake_move_interval: Option<f64>,
+    /// Percent, like `PacmanConfig::ghost_randomness`.
+    pub ghost_randomness: Option<u32>,
+}
+
+/// Everything that can be set from the URL, after validation.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameConfig {
+    pub(crate) game: GameKind,
+    pub(crate) seed: Option<u64>,
+    pub(crate) difficulty: Difficulty,
+    pub(crate) speed: f64,
+    pub(crate) mode: Mode,
+    pub(crate) board: Option<(u32, u32)>,
+    pub(crate) maze: MazeKind,
+    /// Players sharing the keyboard: 1, or 2 for hot-seat versus.
+    pub(crate) players: usize,
+    /// Score to beat when playing a shared challenge.
+    pub(crate) challenge_score: Option<u32>,
+    /// Versus over the network instead of on one keyboard.
+    pub(crate) online: Option<OnlineConfig>,
+    /// Publish the game for spectators.
+    pub(crate) spectate: Option<SpectateConfig>,
+    /// Show somebody else's published game instead of playing.
+    pub(crate) watch: Option<SpectateConfig>,
+    /// Leaderboard server (`src/bin/leaderboard.rs`) to submit finished runs to.
+    pub(crate) leaderboard: Option<String>,
+    /// Seconds idle on the title screen before a bot plays a demo; `None` never.
+    pub(crate) attract: Option<f64>,
+    /// Colours, shape cues, motion and language to draw with; not part of
+    /// challenge tokens.
+    pub(crate) style: Style,
+    /// Swipe threshold and D-pad; like `style`, up to the device played on.
+    pub(crate) touch: TouchConfig,
+    pub(crate) tuning: Tuning,
+}
+
+/// What `effective_config()` hands to JS: the shared options plus the
+/// settings the selected game actually runs with.
+#[derive(Serialize)]
+struct EffectiveConfig<'a> {
+    game: GameKind,
+    seed: Option<u64>,
+    difficulty: Difficulty,
+    speed: f64,
+    mode: Mode,
+    players: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    challenge_score: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    online: Option<&'a OnlineConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spectate: Option<&'a SpectateConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    watch: Option<&'a SpectateConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    leaderboard: Option<&'a str>,
+    attract: Option<f64>,
+    theme: Theme,
+    cues: bool,
+    reduced_motion: bool,
+    language: Language,
+    touch: TouchConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flappy: Option<&'a FlappyConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snake: Option<&'a SnakeConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pacman: Option<&'a PacmanConfig>,
+}
+
+impl GameConfig {
+    pub fn new(game: GameKind) -> GameConfig {
+        GameConfig {
+            game,
+            seed: None,
+            difficulty: Difficulty::Normal,
+            speed: 1.0,
+            mode: Mode::Classic,
+            board: None,
+            maze: MazeKind::Prim,
+            players: 1,
+            challenge_score: None,
+            online: None,
+            spectate: None,
+            watch: None,
+            leaderboard: None,
+            attract: Some(DEFAULT_ATTRACT_DELAY),
+            style: Style::default(),
+            touch: TouchConfig::default(),
+            tuning: Tuning::default(),
+        }
+    }
+
+    /// Parses the options from any key/value source (query string, element attributes).
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<GameConfig, ConfigError> {
+        let leaderboard = lookup("leaderboard").map(parse_leaderboard).transpose()?;
+        let attract = match lookup("attract") {
+            None => Some(DEFAULT_ATTRACT_DELAY),
+            Some(value) => parse_attract(value)?,
+        };
+        let style = Style {
+            theme: lookup("theme").map(parse_theme).transpose()?.unwrap_or_default(),
+            cues: lookup("cues").map(|value| parse_switch("cues", value)).transpose()?.unwrap_or(false),
+            reduced_motion: lookup("motion").map(parse_motion).transpose()?.unwrap_or(false),
+            language: lookup("lang").map(|tags| Language::negotiate(&tags)).unwrap_or_default(),
+        };
+        let touch = TouchConfig {
+            swipe_threshold: lookup("swipe")
+                .map(parse_swipe_threshold)
+                .transpose()?
+                .unwrap_or(DEFAULT_SWIPE_THRESHOLD),
+            dpad: lookup("dpad").map(|value| parse_switch("dpad", value)).transpose()?.unwrap_or(false),
+        };
+
+        // ✅ A challenge link carries every setting itself
+        if let Some(token) = lookup("challenge") {
+            let (mut config, score) = challenge::decode(&token)?;
+            config.challenge_score = Some(score);
+            config.leaderboard = leaderboard;
+            config.attract = attract;
+            config.style = style;
+            config.touch = touch;
+            return Ok(config);
+        }
+
+        let game = match lookup("game") {
+            None => GameKind::Flappy,
+            Some(name) => GameKind::from_name(&name).ok_or(ConfigError::Invalid {
+                param: "game",
+                value: name,
+                expected: "one of flappy, snake, pacman",
+            })?,
+        };
+
+        let mut config = GameConfig::new(game);
+
+        if let Some(value) = lookup("seed") {
+            config.seed = Some(value.trim().parse().map_err(|_| ConfigError::Invalid {
+                param: "seed",
+                value,
+                expected: "a whole number",
+            })?);
+        }
+
+        if let Some(value) = lookup("difficulty") {
+            config.difficulty = match value.trim().to_ascii_lowercase().as_str() {
+                "easy" => Difficulty::Easy,
+                "normal" => Difficulty::Normal,
+                "hard" => Difficulty::Hard,
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        param: "difficulty",
+                        value,
+                        expected: "one of easy, normal, hard",
+                    })
+                }
+            };
+        }
+
+        if let Some(value) = lookup("speed") {
+            config.speed = match value.trim().parse::<f64>() {
+                // ✅ Hundredths are plenty, and keep challenge links exact
+                Ok(speed) if (MIN_SPEED..=MAX_SPEED).contains(&speed) => (speed * 100.0).round() / 100.0,
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        param: "speed",
+                        value,
+                        expected: "a number between 0.25 and 4",
+                    })
+                }
+            };
+        }
+
+        if let Some(value) = lookup("mode") {
+            config.mode = match value.trim().to_ascii_lowercase().as_str() {
+                "classic" => Mode::Classic,
+                "wrap" if game == GameKind::Snake => Mode::Wrap,
+                "wrap" => return Err(ConfigError::Unsupported { param: "mode=wrap", game }),
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        param: "mode",
+                        value,
+                        expected: "one of classic, wrap",
+                    })
+                }
+            };
+        }
+
+        if let Some(value) = lookup("board") {
+            if game != GameKind::Snake {
+                return Err(ConfigError::Unsupported { param: "board", game });
+            }
+            config.board = Some(parse_board(&value).ok_or(ConfigError::Invalid {
+                param: "board",
+                value,
+                expected: "a size like 20 or 30x20, each side between 8 and 60",
+            })?);
+        }
+
+        if let Some(value) = lookup("maze") {
+            if game != GameKind::Pacman {
+                return Err(ConfigError::Unsupported { param: "maze", game });
+            }
+            config.maze = match value.trim().to_ascii_lowercase().as_str() {
+                "prim" => MazeKind::Prim,
+                "corridors" => MazeKind::Corridors,
+                "open" => MazeKind::Open,
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        param: "maze",
+                        value,
+                        expected: "one of prim, corridors, open",
+                    })
+                }
+            };
+        }
+
+        if let Some(value) = lookup("players") {
+            config.players = match value.trim() {
+                "1" => 1,
+                "2" => 2,
+                _ => {
+                    return Err(ConfigError::Invalid {
+                        param: "players",
+                        value,
+                        expected: "1 or 2",
+                    })
+                }
+            };
+        }
+
+        if let Some(server) = lookup("online") {
+            if game != GameKind::Snake {
+                return Err(ConfigError::Unsupported { param: "online", game });
+            }
+            let server = parse_relay("online", server)?;
+            let room = lookup("room").unwrap_or_else(|| DEFAULT_ROOM.to_string());
+            config.online = Some(OnlineConfig { server, room: room.trim().to_string() });
+            config.players = net::ROOM_SIZE; // ✅ Always versus
+        }
+
+        let relay = lookup("relay").map(|relay| parse_relay("relay", relay)).transpose()?;
+        let channel = |name: String| SpectateConfig { channel: name.trim().to_string(), relay: relay.clone() };
+        config.spectate = lookup("spectate").map(channel);
+        config.watch = lookup("watch").map(channel);
+        config.leaderboard = leaderboard;
+        config.attract = attract;
+        config.style = style;
+        config.touch = touch;
+
+        Ok(config)
+    }
+
+    /// Checks settings that didn't come through `from_lookup`, e.g. from a challenge token.
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        if !(MIN_SPEED..=MAX_SPEED).contains(&self.speed) {
+            return Err(ConfigError::Invalid {
+                param: "speed",
+                value: self.speed.to_string(),
+                expected: "a number between 0.25 and 4",
+            });
+        }
+        if self.mode == Mode::Wrap && self.game != GameKind::Snake {
+            return Err(ConfigError::Unsupported { param: "mode=wrap", game: self.game });
+        }
+        if let Some((width, height)) = self.board {
+            if self.game != GameKind::Snake {
+                return Err(ConfigError::Unsupported { param: "board", game: self.game });
+            }
+            let valid = MIN_BOARD..=MAX_BOARD;
+            if !valid.contains(&width) || !valid.contains(&height) {
+                return Err(ConfigError::Invalid {
+                    param: "board",
+                    value: format!("{}x{}", width, height),
+                    expected: "each side between 8 and 60",
+                });
+            }
+        }
+        if self.maze != MazeKind::Prim && self.game != GameKind::Pacman {
+            return Err(ConfigError::Unsupported { param: "maze", game: self.game });
+        }
+        Ok(())
+    }
+
+    pub fn game(&self) -> GameKind {
+        self.game
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    pub fn online(&self) -> Option<&OnlineConfig> {
+        self.online.as_ref()
+    }
+
+    pub fn spectate(&self) -> Option<&SpectateConfig> {
+        self.spectate.as_ref()
+    }
+
+    pub fn watch(&self) -> Option<&SpectateConfig> {
+        self.watch.as_ref()
+    }
+
+    pub fn leaderboard(&self) -> Option<&str> {
+        self.leaderboard.as_deref()
+    }
+
+    pub fn attract(&self) -> Option<f64> {
+        self.attract
+    }
+
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    pub fn touch(&self) -> TouchConfig {
+        self.touch
+    }
+
+    pub fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+    }
+
+    pub fn flappy(&self) -> FlappyConfig {
+        let pipe_gap = match self.difficulty {
+            Difficulty::Easy => flappy::PIPE_GAP + 30.0,
+            Difficulty::Normal => flappy::PIPE_GAP,
+            Difficulty::Hard => flappy::PIPE_GAP - 30.0,
+        };
+
+        // ✅ Slowing time by `speed` scales speeds by it and accelerations by its square,
+        // so the bird flies the same arcs, only slower
+        FlappyConfig {
+            gravity: self.tuning.gravity.unwrap_or(flappy::GRAVITY * self.speed * self.speed),
+            jump_strength: flappy::JUMP_STRENGTH * self.speed,
+            max_fall_speed: flappy::MAX_FALL_SPEED * self.speed,
+            pipe_gap: self.tuning.pipe_gap.unwrap_or(pipe_gap),
+            pipe_spacing: flappy::PIPE_SPACING,
+            pipe_speed: flappy::PIPE_SPEED * self.speed,
+        }
+    }
+
+    pub fn snake(&self) -> SnakeConfig {
+        let move_interval = match self.difficulty {
+            Difficulty::Easy => snake::SNAKE_MOVE_INTERVAL * 4.0 / 3.0,
+            Difficulty::Normal => snake::SNAKE_MOVE_INTERVAL,
+            Difficulty::Hard => snake::SNAKE_MOVE_INTERVAL * 2.0 / 3.0,
+        };
+        let (width, height) = self.board.unwrap_or((snake::WIDTH, snake::HEIGHT));
+
+        SnakeConfig {
+            width,
+            height,
+            move_interval: self.tuning.snake_move_interval.unwrap_or(move_interval / self.speed),
+            wrap: self.mode == Mode::Wrap,
+        }
+    }
+
+    pub fn pacman(&self) -> PacmanConfig {
+        let (ghost_move_interval, ghost_randomness) = match self.difficulty {
+            Difficulty::Easy => (pacman::GHOST_MOVE_INTERVAL * 1.2, 50),
+            Difficulty::Normal => (pacman::GHOST_MOVE_INTERVAL, pacman::GHOST_RANDOMNESS),
+            Difficulty::Hard => (pacman::GHOST_MOVE_INTERVAL * 0.8, 15),
+        };
+
+        PacmanConfig {
+            maze: self.maze,
+            pacman_move_interval: pacman::PACMAN_MOVE_INTERVAL / self.speed,
+            ghost_move_interval: ghost_move_interval / self.speed,
+            ghost_randomness: self.tuning.ghost_randomness.unwrap_or(ghost_randomness),
+        }
+    }
+
+    /// Canvas size in pixels the configured game renders into.
+    pub fn canvas_size(&self) -> (u32, u32) {
+        match self.game {
+            GameKind::Snake => {
+                let snake = self.snake();
+                (
+                    (snake.width as f64 * snake::GRID_SIZE) as u32,
+                    (snake.height as f64 * snake::GRID_SIZE) as u32,
+                )
+            }
+            game => game.canvas_size(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl GameConfig {
+    /// Reads the options from `window.location.search`.
+    #[wasm_bindgen]
+    pub fn from_query() -> Result<GameConfig, JsValue> {
+        let search = window().unwrap().location().search()?;
+        Self::from_query_string(&search)
+    }
+
+    /// Parses a query string such as `?game=snake&board=30x20`.
+    #[wasm_bindgen]
+    pub fn from_query_string(query: &str) -> Result<GameConfig, JsValue> {
+        let params = UrlSearchParams::new_with_str(query)?;
+        Ok(Self::from_lookup(|key| params.get(key))?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn game_name(&self) -> String {
+        self.game.name().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn challenge_score(&self) -> Option<u32> {
+        self.challenge_score
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn canvas_width(&self) -> u32 {
+        self.canvas_size().0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn canvas_height(&self) -> u32 {
+        self.canvas_size().1
+    }
+
+    /// The settings the game will actually run with, as a plain JS object.
+    #[wasm_bindgen]
+    pub fn effective_config(&self) -> Result<JsValue, JsValue> {
+        let (flappy, snake, pacman) = (self.flappy(), self.snake(), self.pacman());
+        let effective = EffectiveConfig {
+            game: self.game,
+            seed: self.seed,
+            difficulty: self.difficulty,
+            speed: self.speed,
+            mode: self.mode,
+            players: self.players,
+            challenge_score: self.challenge_score,
+            online: self.online.as_ref(),
+            spectate: self.spectate.as_ref(),
+            watch: self.watch.as_ref(),
+            leaderboard: self.leaderboard.as_deref(),
+            attract: self.attract,
+            theme: self.style.theme,
+            cues: self.style.cues,
+            reduced_motion: self.style.reduced_motion,
+            language: self.style.language,
+            touch: self.touch,
+            flappy: (self.game == GameKind::Flappy).then_some(&flappy),
+            snake: (self.game == GameKind::Snake).then_some(&snake),
+            pacman: (self.game == GameKind::Pacman).then_some(&pacman),
+        };
+        let json = serde_json::to_string(&effective).map_err(|err| JsError::new(&err.to_string()))?;
+        js_sys::JSON::parse(&json)
+    }
+}
+
+fn parse_relay(param: &'static str, value: String) -> Result<String, ConfigError> {
+    let value = value.trim().to_string();
+    if value.starts_with("ws://") || value.starts_with("wss://") {
+        Ok(value)
+    } else {
+        Err(ConfigError::Invalid {
+            param,
+            value,
+            expected: "a relay address like ws://localhost:9001",
+        })
+    }
+}
+
+fn parse_leaderboard(value: String) -> Result<String, ConfigError> {
+    let value = value.trim().trim_end_matches('/').to_string();
+    if value.starts_with("http://") || value.starts_with("https://") {
+        Ok(value)
+    } else {
+        Err(ConfigError::Invalid {
+            param: "leaderboard",
+            value,
+            expected: "a server address like http://localhost:9002",
+        })
+    }
+}
+
+fn parse_attract(value: String) -> Result<Option<f64>, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" | "0" => Ok(None),
+        seconds => match seconds.parse::<f64>() {
+            Ok(seconds) if seconds > 0.0 && seconds <= MAX_ATTRACT_DELAY => Ok(Some(seconds)),
+            _ => Err(ConfigError::Invalid {
+                param: "attract",
+                value,
+                expected: "seconds up to 600, or off",
+            }),
+        },
+    }
+}
+
+pub(crate) fn parse_theme(value: String) -> Result<Theme, ConfigError> {
+    Theme::from_name(&value).ok_or(ConfigError::Invalid {
+        param: "theme",
+        value,
+        expected: "one of classic, neon, gameboy, light, site, deuteranopia, protanopia, contrast",
+    })
+}
+
+fn parse_switch(param: &'static str, value: String) -> Result<bool, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" | "1" | "true" => Ok(true),
+        "off" | "0" | "false" => Ok(false),
+        _ => Err(ConfigError::Invalid {
+            param,
+            value,
+            expected: "on or off",
+        }),
+    }
+}
+
+fn parse_swipe_threshold(value: String) -> Result<f64, ConfigError> {
+    match value.trim().parse::<f64>() {
+        Ok(pixels) if (MIN_SWIPE_THRESHOLD..=MAX_SWIPE_THRESHOLD).contains(&pixels) => Ok(pixels),
+        _ => Err(ConfigError::Invalid {
+            param: "swipe",
+            value,
+            expected: "pixels between 4 and 200",
+        }),
+    }
+}
+
+fn parse_motion(value: String) -> Result<bool, ConfigError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "reduced" | "reduce" => Ok(true),
+        "full" | "no-preference" => Ok(false),
+        _ => Err(ConfigError::Invalid {
+            param: "motion",
+            value,
+            expected: "reduced or full",
+        }),
+    }
+}
+
+fn parse_board(value: &str) -> Option<(u32, u32)> {
+    let value = value.trim().to_ascii_lowercase();
+    let (width, height) = match value.split_once('x') {
+        Some((width, height)) => (width.trim().parse().ok()?, height.trim().parse().ok()?),
+        None => {
+            let size = value.parse().ok()?;
+            (size, size)
+        }
+    };
+
+    let valid = MIN_BOARD..=MAX_BOARD;
+    (valid.contains(&width) && valid.contains(&height)).then_some((width, height))
+}
@@ -0,0 +1,191 @@
+//! The slice of the 2D canvas API the games draw with, so a frame can go to
+//! a browser canvas or be recorded into a `DisplayList`: plain data that
+//! snapshot tests (`tests/snapshots.rs`) compare against golden files.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use wasm_bindgen::JsValue;
+use web_sys::CanvasRenderingContext2d;
+
+use crate::theme::Style;
+use crate::view::View;
+
+/// Where `draw` functions draw. Method names and results follow
+/// `CanvasRenderingContext2d`, which implements it by drawing.
+pub trait Canvas {
+    fn set_fill_style_str(&self, color: &str);
+    fn set_stroke_style_str(&self, color: &str);
+    fn set_line_width(&self, width: f64);
+    fn set_font(&self, font: &str);
+    fn set_text_align(&self, align: &str);
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn begin_path(&self);
+    fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) -> Result<(), JsValue>;
+    fn fill(&self);
+    fn stroke(&self);
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue>;
+}
+
+impl Canvas for CanvasRenderingContext2d {
+    fn set_fill_style_str(&self, color: &str) {
+        CanvasRenderingContext2d::set_fill_style_str(self, color);
+    }
+
+    fn set_stroke_style_str(&self, color: &str) {
+        CanvasRenderingContext2d::set_stroke_style_str(self, color);
+    }
+
+    fn set_line_width(&self, width: f64) {
+        CanvasRenderingContext2d::set_line_width(self, width);
+    }
+
+    fn set_font(&self, font: &str) {
+        CanvasRenderingContext2d::set_font(self, font);
+    }
+
+    fn set_text_align(&self, align: &str) {
+        CanvasRenderingContext2d::set_text_align(self, align);
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        CanvasRenderingContext2d::fill_rect(self, x, y, width, height);
+    }
+
+    fn begin_path(&self) {
+        CanvasRenderingContext2d::begin_path(self);
+    }
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::arc(self, x, y, radius, start_angle, end_angle)
+    }
+
+    fn fill(&self) {
+        CanvasRenderingContext2d::fill(self);
+    }
+
+    fn stroke(&self) {
+        CanvasRenderingContext2d::stroke(self);
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue> {
+        CanvasRenderingContext2d::fill_text(self, text, x, y)
+    }
+}
+
+/// One recorded canvas call.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    FillStyle(String),
+    StrokeStyle(String),
+    LineWidth(f64),
+    Font(String),
+    TextAlign(String),
+    FillRect { x: f64, y: f64, width: f64, height: f64 },
+    BeginPath,
+    Arc { x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64 },
+    Fill,
+    Stroke,
+    FillText { text: String, x: f64, y: f64 },
+}
+
+/// One call per line, e.g. `fill_rect 0 0 400 600`; text is quoted.
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawCommand::FillStyle(color) => write!(f, "fill_style {}", color),
+            DrawCommand::StrokeStyle(color) => write!(f, "stroke_style {}", color),
+            DrawCommand::LineWidth(width) => write!(f, "line_width {}", width),
+            DrawCommand::Font(font) => write!(f, "font {}", font),
+            DrawCommand::TextAlign(align) => write!(f, "text_align {}", align),
+            DrawCommand::FillRect { x, y, width, height } => write!(f, "fill_rect {} {} {} {}", x, y, width, height),
+            DrawCommand::BeginPath => write!(f, "begin_path"),
+            DrawCommand::Arc { x, y, radius, start_angle, end_angle } => {
+                write!(f, "arc {} {} {} {} {}", x, y, radius, start_angle, end_angle)
+            }
+            DrawCommand::Fill => write!(f, "fill"),
+            DrawCommand::Stroke => write!(f, "stroke"),
+            DrawCommand::FillText { text, x, y } => write!(f, "fill_text {:?} {} {}", text, x, y),
+        }
+    }
+}
+
+/// A canvas that records calls instead of drawing them.
+#[derive(Debug, Default)]
+pub struct DisplayList {
+    commands: RefCell<Vec<DrawCommand>>,
+}
+
+impl DisplayList {
+    /// Everything `view` draws in `style`.
+    pub fn record(view: &View, style: Style) -> DisplayList {
+        let list = DisplayList::default();
+        view.draw(&list, &style);
+        list
+    }
+
+    pub fn commands(&self) -> Vec<DrawCommand> {
+        self.commands.borrow().clone()
+    }
+
+    fn push(&self, command: DrawCommand) {
+        self.commands.borrow_mut().push(command);
+    }
+}
+
+impl fmt::Display for DisplayList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for command in self.commands.borrow().iter() {
+            writeln!(f, "{}", command)?;
+        }
+        Ok(())
+    }
+}
+
+impl Canvas for DisplayList {
+    fn set_fill_style_str(&self, color: &str) {
+        self.push(DrawCommand::FillStyle(color.to_string()));
+    }
+
+    fn set_stroke_style_str(&self, color: &str) {
+        self.push(DrawCommand::StrokeStyle(color.to_string()));
+    }
+
+    fn set_line_width(&self, width: f64) {
+        self.push(DrawCommand::LineWidth(width));
+    }
+
+    fn set_font(&self, font: &str) {
+        self.push(DrawCommand::Font(font.to_string()));
+    }
+
+    fn set_text_align(&self, align: &str) {
+        self.push(DrawCommand::TextAlign(align.to_string()));
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.push(DrawCommand::FillRect { x, y, width, height });
+    }
+
+    fn begin_path(&self) {
+        self.push(DrawCommand::BeginPath);
+    }
+
+    fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) -> Result<(), JsValue> {
+        self.push(DrawCommand::Arc { x, y, radius, start_angle, end_angle });
+        Ok(())
+    }
+
+    fn fill(&self) {
+        self.push(DrawCommand::Fill);
+    }
+
+    fn stroke(&self) {
+        self.push(DrawCommand::Stroke);
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue> {
+        self.push(DrawCommand::FillText { text: text.to_string(), x, y });
+        Ok(())
+    }
+}
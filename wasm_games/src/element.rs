@@ -0,0 +1,354 @@
+//! `<wasm-game>` custom element, so markdown blog posts can embed a game without
+//! any inline script:
+//!
+//! ```html
+//! <script type="module">import init from '/wasm_games/pkg/wasm_games.js'; init();</script>
+//! <wasm-game name="snake" difficulty="hard" board="30x20"></wasm-game>
+//! ```
+//!
+//! The element creates its own canvas sized for the game, draws a still frame,
+//! starts the loop on the first click, key press or gamepad (or for the
+//! attract demo once nobody has for a while) and tears everything down when
+//! it is removed from the page. With several on a page, gamepads play
+//! whichever was focused last.
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use wasm_bindgen::prelude::*;
+use web_sys::{window, Event, EventTarget, HtmlCanvasElement, HtmlElement, KeyboardEvent, PointerEvent};
+
+use crate::config::GameConfig;
+use crate::game::Game;
+use crate::utils;
+
+pub const TAG_NAME: &str = "wasm-game";
+
+const FIXED_TIMESTEP: f64 = 1.0 / 60.0; // ✅ Same fixed step as index.html
+const MAX_DELTA_TIME: f64 = 0.1; // ✅ Prevent big time jumps
+
+#[wasm_bindgen(inline_js = r#"
+export function define_game_element(tag, connected, disconnected) {
+    if (customElements.get(tag)) {
+        return;
+    }
+    customElements.define(tag, class extends HTMLElement {
+        connectedCallback() { connected(this); }
+        disconnectedCallback() { disconnected(this); }
+    });
+}
+"#)]
+extern "C" {
+    fn define_game_element(
+        tag: &str,
+        connected: &Closure<dyn FnMut(HtmlElement)>,
+        disconnected: &Closure<dyn FnMut(HtmlElement)>,
+    );
+}
+
+type FrameCallback = Closure<dyn FnMut(f64)>;
+type Listener = (EventTarget, &'static str, Closure<dyn FnMut(Event)>);
+type Timer = (i32, Closure<dyn FnMut()>);
+
+thread_local! {
+    static EMBEDS: RefCell<Vec<Embed>> = const { RefCell::new(Vec::new()) };
+    /// The embed focused most recently, the only one gamepads start and play.
+    static LAST_FOCUSED: RefCell<Weak<Runner>> = const { RefCell::new(Weak::new()) };
+}
+
+/// Registers `<wasm-game>` with the page's custom element registry.
+pub fn register() {
+    let connected = Closure::<dyn FnMut(HtmlElement)>::new(|element: HtmlElement| {
+        if let Err(err) = connect(&element) {
+            web_sys::console::error_2(&"wasm-game: failed to start".into(), &err);
+        }
+    });
+    let disconnected = Closure::<dyn FnMut(HtmlElement)>::new(|element: HtmlElement| {
+        disconnect(&element);
+    });
+
+    define_game_element(TAG_NAME, &connected, &disconnected);
+
+    // The element definition lives as long as the page does.
+    connected.forget();
+    disconnected.forget();
+}
+
+/// Drives one game from `requestAnimationFrame` with a fixed update step.
+struct Runner {
+    game: RefCell<Game>,
+    last_time: Cell<Option<f64>>,
+    accumulated_time: Cell<f64>,
+    frame_id: Cell<Option<i32>>,
+    frame: RefCell<Option<FrameCallback>>,
+}
+
+impl Runner {
+    fn new(game: Game) -> Rc<Runner> {
+        Rc::new(Runner {
+            game: RefCell::new(game),
+            last_time: Cell::new(None),
+            accumulated_time: Cell::new(0.0),
+            frame_id: Cell::new(None),
+            frame: RefCell::new(None),
+        })
+    }
+
+    fn is_running(&self) -> bool {
+        self.frame.borrow().is_some()
+    }
+
+    fn start(self: &Rc<Self>) {
+        if self.is_running() {
+            return;
+        }
+
+        let runner: Weak<Runner> = Rc::downgrade(self);
+        *self.frame.borrow_mut() = Some(Closure::new(move |now: f64| {
+            if let Some(runner) = runner.upgrade() {
+                runner.tick(now);
+            }
+        }));
+        self.request_frame();
+    }
+
+    fn tick(&self, now: f64) {
+        let last_time = self.last_time.replace(Some(now)).unwrap_or(now);
+        let delta_time = ((now - last_time) / 1000.0).min(MAX_DELTA_TIME);
+
+        let mut accumulated_time = self.accumulated_time.get() + delta_time;
+        let gamepads = plays_gamepads(self);
+        {
+            let mut game = self.game.borrow_mut();
+            while accumulated_time >= FIXED_TIMESTEP {
+                game.step(FIXED_TIMESTEP, gamepads);
+                accumulated_time -= FIXED_TIMESTEP;
+            }
+            game.render_at(accumulated_time / FIXED_TIMESTEP);
+        }
+        self.accumulated_time.set(accumulated_time);
+
+        self.request_frame();
+    }
+
+    fn request_frame(&self) {
+        let frame = self.frame.borrow();
+        if let Some(frame) = frame.as_ref() {
+            let id = window()
+                .unwrap()
+                .request_animation_frame(frame.as_ref().unchecked_ref())
+                .ok();
+            self.frame_id.set(id);
+        }
+    }
+
+    fn stop(&self) {
+        if let Some(id) = self.frame_id.take() {
+            window().unwrap().cancel_animation_frame(id).ok();
+        }
+        self.frame.borrow_mut().take();
+    }
+}
+
+/// A live `<wasm-game>` instance and everything that has to be released with it.
+struct Embed {
+    element: HtmlElement,
+    canvas: HtmlCanvasElement,
+    runner: Rc<Runner>,
+    listeners: Vec<Listener>,
+    attract_timer: Option<Timer>,
+}
+
+impl Embed {
+    fn listen(&mut self, event: &'static str, handler: impl FnMut(Event) + 'static) -> Result<(), JsValue> {
+        let canvas = self.canvas.clone().into();
+        self.listen_on(canvas, event, handler)
+    }
+
+    fn listen_on(
+        &mut self,
+        target: EventTarget,
+        event: &'static str,
+        handler: impl FnMut(Event) + 'static,
+    ) -> Result<(), JsValue> {
+        let closure = Closure::<dyn FnMut(Event)>::new(handler);
+        target.add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())?;
+        self.listeners.push((target, event, closure));
+        Ok(())
+    }
+
+    fn teardown(self) {
+        if let Some((id, _)) = &self.attract_timer {
+            window().unwrap().clear_timeout_with_handle(*id);
+        }
+        self.runner.stop();
+        for (target, event, closure) in &self.listeners {
+            target
+                .remove_event_listener_with_callback(event, closure.as_ref().unchecked_ref())
+                .ok();
+        }
+        self.canvas.remove();
+    }
+}
+
+fn connect(element: &HtmlElement) -> Result<(), JsValue> {
+    // Custom elements can be connected again after being moved; start fresh.
+    disconnect(element);
+    element.set_text_content(None);
+
+    // Same options as the query string, with the game picked by `name`, and
+    // motion and language following the reader's browser unless set.
+    let config = GameConfig::from_lookup(|key| match key {
+        "game" => element.get_attribute("name"),
+        "motion" => element
+            .get_attribute("motion")
+            .or_else(|| utils::prefers_reduced_motion().then(|| "reduced".to_string())),
+        "lang" => element.get_attribute("lang").or_else(utils::browser_languages),
+        key => element.get_attribute(key),
+    });
+    let config = match config {
+        Ok(config) => config,
+        Err(err) => {
+            element.set_text_content(Some(&format!("wasm-game: {}", err)));
+            return Ok(());
+        }
+    };
+
+    let document = window().unwrap().document().unwrap();
+    let canvas = document
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()?;
+    let (width, height) = config.canvas_size();
+    canvas.set_width(width);
+    canvas.set_height(height);
+    canvas.set_tab_index(0); // ✅ Focusable, so keys only go to the game that was clicked
+    canvas.style().set_property("display", "block")?;
+    canvas.style().set_property("max-width", "100%")?;
+    canvas.style().set_property("outline", "none")?;
+    canvas.style().set_property("touch-action", "none")?; // ✅ Swipes steer instead of scrolling the page
+    canvas.style().set_property("border", &format!("2px solid {}", config.style().palette().border))?;
+    element.append_child(&canvas)?;
+
+    let game = Game::new(&config, canvas.clone())?;
+    game.render(); // ✅ Still frame until the first interaction
+    let runner = Runner::new(game);
+
+    let mut embed = Embed {
+        element: element.clone(),
+        canvas: canvas.clone(),
+        runner: runner.clone(),
+        listeners: Vec::new(),
+        attract_timer: None,
+    };
+
+    let attract_delay = runner.game.borrow().attract_delay();
+    if let Some(delay) = attract_delay {
+        let timer_runner = runner.clone();
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            if !timer_runner.is_running() {
+                // ✅ Nobody has played yet: the loop only starts for the demo
+                timer_runner.game.borrow_mut().start_attract();
+                timer_runner.start();
+            }
+        });
+        let id = window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), (delay * 1000.0) as i32)?;
+        embed.attract_timer = Some((id, closure));
+    }
+
+    // Pointer events only: they cover mouse, touch and pen alike, and a tap
+    // doesn't also arrive as a `mousedown`.
+    let pointer_runner = runner.clone();
+    let pointer_canvas = canvas.clone();
+    embed.listen("pointerdown", move |event| {
+        let event: PointerEvent = event.unchecked_into();
+        event.prevent_default();
+        pointer_canvas.focus().ok();
+        pointer_canvas.set_pointer_capture(event.pointer_id()).ok(); // ✅ Keep following a swipe off the canvas
+        pointer_runner.start();
+        let (x, y) = canvas_point(&pointer_canvas, &event);
+        pointer_runner.game.borrow_mut().pointer_down(event.pointer_id(), x, y);
+    })?;
+
+    let pointer_runner = runner.clone();
+    let pointer_canvas = canvas.clone();
+    embed.listen("pointermove", move |event| {
+        let event: PointerEvent = event.unchecked_into();
+        let (x, y) = canvas_point(&pointer_canvas, &event);
+        pointer_runner.game.borrow_mut().pointer_move(event.pointer_id(), x, y);
+    })?;
+
+    for name in ["pointerup", "pointercancel"] {
+        let pointer_runner = runner.clone();
+        embed.listen(name, move |event| {
+            let event: PointerEvent = event.unchecked_into();
+            pointer_runner.game.borrow_mut().pointer_up(event.pointer_id());
+        })?;
+    }
+
+    // Clicking focuses the canvas too, so this follows pointer and keyboard.
+    let focus_runner = Rc::downgrade(&runner);
+    embed.listen("focus", move |_event| {
+        LAST_FOCUSED.with(|last| *last.borrow_mut() = focus_runner.clone());
+    })?;
+
+    // ✅ Gamepads are polled by the loop, so a pad showing up starts it, but
+    // only in the one game on the page it's meant for
+    let pad_runner = runner.clone();
+    embed.listen_on(window().unwrap().into(), "gamepadconnected", move |_event| {
+        if plays_gamepads(&pad_runner) {
+            pad_runner.start();
+        }
+    })?;
+
+    let key_runner = runner.clone();
+    embed.listen("keydown", move |event| {
+        let event: KeyboardEvent = event.unchecked_into();
+        key_runner.start();
+        if key_runner.game.borrow_mut().key_down(&event.key(), &event.code()) {
+            event.prevent_default(); // ✅ Don't scroll the blog post while playing
+        }
+    })?;
+
+    let key_runner = runner;
+    embed.listen("keyup", move |event| {
+        let event: KeyboardEvent = event.unchecked_into();
+        if key_runner.game.borrow_mut().key_up(&event.key(), &event.code()) {
+            event.prevent_default();
+        }
+    })?;
+
+    EMBEDS.with(|embeds| embeds.borrow_mut().push(embed));
+    Ok(())
+}
+
+/// Whether `runner` is the embed gamepads start and play: the one focused
+/// last, or the first on the page if none still around has been.
+fn plays_gamepads(runner: &Runner) -> bool {
+    if let Some(focused) = LAST_FOCUSED.with(|last| last.borrow().upgrade()) {
+        return std::ptr::eq(Rc::as_ptr(&focused), runner);
+    }
+    let first = EMBEDS.with(|embeds| embeds.borrow().first().map(|embed| Rc::as_ptr(&embed.runner)));
+    first.is_some_and(|first| std::ptr::eq(first, runner))
+}
+
+/// Where `event` happened in canvas pixels, which CSS may have scaled.
+fn canvas_point(canvas: &HtmlCanvasElement, event: &PointerEvent) -> (f64, f64) {
+    let scale = canvas.width() as f64 / canvas.client_width().max(1) as f64;
+    (event.offset_x() as f64 * scale, event.offset_y() as f64 * scale)
+}
+
+fn disconnect(element: &HtmlElement) {
+    let removed = EMBEDS.with(|embeds| {
+        let mut embeds = embeds.borrow_mut();
+        embeds
+            .iter()
+            .position(|embed| &embed.element == element)
+            .map(|index| embeds.remove(index))
+    });
+
+    if let Some(embed) = removed {
+        embed.teardown();
+    }
+}
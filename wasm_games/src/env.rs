@@ -0,0 +1,227 @@
+//! Reinforcement-learning environment over the shipped game rules, in the
+//! usual `reset(seed)` / `step(action) -> (observation, reward, done)` shape.
+//! Works natively and from JS:
+//!
+//! ```js
+//! const env = new Env(GameConfig.from_query_string('game=snake'), 'grid');
+//! let observation = env.reset(42n);
+//! const step = env.step(4); // ✅ step.observation, step.reward, step.done
+//! ```
+//!
+//! Actions are small integers: Flappy `0` waits and `1` flaps; Snake and
+//! Pac-Man take `0` (Snake keeps going, Pac-Man stands still) or `1`..`4` for
+//! up, down, left, right. A run starts with its first flap or direction.
+//! Reward is the score gained by the step, with `-1` for the step that ends
+//! the run.
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::{ConfigError, GameConfig};
+use crate::events::GameEvent;
+use crate::game::GameKind;
+use crate::lockstep::TICK;
+use crate::sim::{self, Command, Sim};
+
+/// Steps before `done` even if the run goes on, so idle policies end too.
+const DEFAULT_MAX_STEPS: u32 = 50_000;
+/// Flappy ticks per step; the grid games step once per move instead.
+const FLAPPY_TICKS_PER_STEP: u32 = 4;
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObservationKind {
+    /// Layers of 0/1 cells: `[layers, rows, columns]`.
+    Grid,
+    /// A short vector of hand-picked values, mostly scaled to about -1..1.
+    Features,
+}
+
+impl ObservationKind {
+    pub fn from_name(name: &str) -> Option<ObservationKind> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "grid" => Some(ObservationKind::Grid),
+            "features" => Some(ObservationKind::Features),
+            _ => None,
+        }
+    }
+}
+
+/// A flat observation, row-major in `shape` order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+impl Observation {
+    pub(crate) fn grid(layers: usize, width: usize, height: usize) -> Observation {
+        Observation { shape: vec![layers, height, width], data: vec![0.0; layers * width * height] }
+    }
+
+    pub(crate) fn features(data: Vec<f32>) -> Observation {
+        Observation { shape: vec![data.len()], data }
+    }
+
+    /// Marks cell `(x, y)` of `layer` in a grid; off-grid cells are ignored.
+    pub(crate) fn set(&mut self, layer: usize, x: usize, y: usize) {
+        let (height, width) = (self.shape[1], self.shape[2]);
+        if x < width && y < height {
+            self.data[(layer * height + y) * width + x] = 1.0;
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub struct Step {
+    observation: Vec<f32>,
+    pub reward: f64,
+    pub done: bool,
+}
+
+#[wasm_bindgen]
+impl Step {
+    #[wasm_bindgen(getter)]
+    pub fn observation(&self) -> Vec<f32> {
+        self.observation.clone()
+    }
+}
+
+impl Step {
+    pub fn into_observation(self) -> Vec<f32> {
+        self.observation
+    }
+}
+
+#[wasm_bindgen]
+pub struct Env {
+    config: GameConfig,
+    kind: ObservationKind,
+    sim: Box<dyn Sim>,
+    ticks_per_step: u32,
+    max_steps: u32,
+    steps: u32,
+    score: u32,
+}
+
+#[wasm_bindgen]
+impl Env {
+    /// `observation` is `"grid"` or `"features"`. Solo games only.
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &GameConfig, observation: &str) -> Result<Env, ConfigError> {
+        let kind = ObservationKind::from_name(observation).ok_or_else(|| ConfigError::Invalid {
+            param: "observation",
+            value: observation.to_string(),
+            expected: "grid or features",
+        })?;
+        if config.players() != 1 || config.online().is_some() || config.watch().is_some() {
+            return Err(ConfigError::Invalid {
+                param: "players",
+                value: config.players().to_string(),
+                expected: "a solo, offline game",
+            });
+        }
+
+        let move_interval = match config.game() {
+            GameKind::Flappy => None,
+            GameKind::Snake => Some(config.snake().move_interval),
+            GameKind::Pacman => Some(config.pacman().pacman_move_interval),
+        };
+        Ok(Env {
+            config: config.clone(),
+            kind,
+            sim: sim::new_sim(config),
+            ticks_per_step: move_interval.map_or(FLAPPY_TICKS_PER_STEP, |interval| (interval / TICK).ceil() as u32),
+            max_steps: DEFAULT_MAX_STEPS,
+            steps: 0,
+            score: 0,
+        })
+    }
+
+    /// Starts a fresh run on `seed`; returns the first observation.
+    #[wasm_bindgen]
+    pub fn reset(&mut self, seed: u64) -> Vec<f32> {
+        let mut config = self.config.clone();
+        config.seed = Some(seed);
+        self.sim = sim::new_sim(&config);
+        self.steps = 0;
+        self.score = 0;
+        self.observe().data
+    }
+
+    #[wasm_bindgen]
+    pub fn step(&mut self, action: u32) -> Step {
+        if let Some(command) = self.command(action) {
+            self.sim.command(command);
+            self.sim.take_events();
+        }
+
+        let mut reward = 0.0;
+        let mut done = false;
+        for _ in 0..self.ticks_per_step {
+            self.sim.update(TICK);
+            for event in self.sim.take_events() {
+                if let GameEvent::GameOver { score } = event {
+                    reward += score as f64 - self.score as f64 - 1.0;
+                    self.score = 0;
+                    done = true;
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        if !done {
+            let score = self.sim.scores()[0];
+            reward += score as f64 - self.score as f64;
+            self.score = score;
+        }
+
+        self.steps += 1;
+        Step {
+            observation: self.observe().data,
+            reward,
+            done: done || self.steps >= self.max_steps,
+        }
+    }
+
+    /// Dimensions of every observation, e.g. `[layers, rows, columns]`.
+    #[wasm_bindgen]
+    pub fn observation_shape(&self) -> Vec<u32> {
+        self.observe().shape.into_iter().map(|size| size as u32).collect()
+    }
+
+    #[wasm_bindgen]
+    pub fn action_count(&self) -> u32 {
+        match self.config.game() {
+            GameKind::Flappy => 2,
+            GameKind::Snake | GameKind::Pacman => 1 + DIRECTIONS.len() as u32,
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps;
+    }
+
+    /// Game ticks (1/60 s) each step plays.
+    #[wasm_bindgen]
+    pub fn ticks_per_step(&self) -> u32 {
+        self.ticks_per_step
+    }
+}
+
+impl Env {
+    pub fn observe(&self) -> Observation {
+        self.sim.observe(self.kind)
+    }
+
+    fn command(&self, action: u32) -> Option<Command> {
+        let direction = (action as usize).checked_sub(1).and_then(|i| DIRECTIONS.get(i));
+        match (self.config.game(), direction) {
+            (GameKind::Flappy, _) => (action == 1).then_some(Command::Flap(0)),
+            (GameKind::Snake, None) => None,
+            (GameKind::Pacman, None) => Some(Command::Steer(0, 0, 0)),
+            (_, Some(&(dx, dy))) => Some(Command::Steer(0, dx, dy)),
+        }
+    }
+}
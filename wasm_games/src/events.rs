@@ -0,0 +1,46 @@
+/// Things that happen during play, reported by the games to anything that
+/// reacts to them (achievements, ...).
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    /// First input of a run.
+    Started,
+    /// Flappy cleared a pipe; `total` in this run so far.
+    PipePassed { total: u32 },
+    /// Snake ate and is now `length` segments long.
+    SnakeGrew { length: usize },
+    /// Pac-Man ate the last pellet; `stopped` if he stood still at any point.
+    MazeCleared { stopped: bool },
+    /// Pac-Man ate a frightened ghost, the `streak`th on the current power pellet.
+    GhostEaten { streak: u32 },
+    /// `player` is out of the run (the run ends once every player is).
+    Died { player: usize, cause: DeathCause },
+    GameOver { score: u32 },
+}
+
+/// What knocked a player out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeathCause {
+    Pipe,
+    Ground,
+    Ceiling,
+    Wall,
+    OwnBody,
+    OtherSnake,
+    HeadOn,
+    Ghost,
+}
+
+impl DeathCause {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeathCause::Pipe => "pipe",
+            DeathCause::Ground => "ground",
+            DeathCause::Ceiling => "ceiling",
+            DeathCause::Wall => "wall",
+            DeathCause::OwnBody => "own_body",
+            DeathCause::OtherSnake => "other_snake",
+            DeathCause::HeadOn => "head_on",
+            DeathCause::Ghost => "ghost",
+        }
+    }
+}
@@ -1,127 +1,500 @@
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use std::f64;
+use rand::Rng;
 
-const WIDTH: f64 = 400.0;
-const HEIGHT: f64 = 600.0;
+use crate::achievements::Achievements;
+use crate::announce::Announcer;
+use crate::challenge;
+use crate::config::{FlappyConfig, GameConfig};
+use crate::draw::Canvas;
+use crate::env::{Observation, ObservationKind};
+use crate::events::{DeathCause, GameEvent};
+use crate::game::{self, GameKind, RunResult};
+use crate::replay::Recorder;
+use crate::rng::{self, GameRng};
+use crate::sim::{Command, Sim};
+use crate::theme::Style;
+use crate::view::{self, Hud, View};
+
+pub(crate) const WIDTH: f64 = 400.0;
+pub(crate) const HEIGHT: f64 = 600.0;
 const BIRD_RADIUS: f64 = 25.0;
-const GRAVITY: f64 = 0.35;
-const JUMP_STRENGTH: f64 = -7.0;
-const PIPE_WIDTH: f64 = 50.0;
-const PIPE_GAP: f64 = 150.0;
+pub(crate) const GRAVITY: f64 = 1260.0; // ✅ Pixels per second, per second
+pub(crate) const JUMP_STRENGTH: f64 = -420.0; // ✅ Pixels per second, upwards
+pub(crate) const MAX_FALL_SPEED: f64 = 600.0;
+pub(crate) const PIPE_WIDTH: f64 = 50.0;
+pub(crate) const BIRD_LEFT: f64 = 30.0; // ✅ Left edge of the bird's hitbox
+pub(crate) const PIPE_GAP: f64 = 150.0;
+pub(crate) const PIPE_SPACING: f64 = 250.0;
+pub(crate) const PIPE_SPEED: f64 = 120.0; // ✅ Pixels per second
+/// Seconds of physics per step. `update` runs whole substeps whatever step
+/// the host passes, so a run plays out the same at 30, 60 or 240 Hz.
+const SUBSTEP: f64 = 1.0 / 240.0;
+/// Slack for float error in the host's step, e.g. 1/60 not being exactly
+/// four substeps.
+const SUBSTEP_TOLERANCE: f64 = 1e-9;
 
-#[wasm_bindgen]
-pub struct FlappyBird {
-    bird_y: f64,
-    bird_velocity: f64,
+struct Bird {
+    y: f64,
+    velocity: f64,
+    alive: bool,
+    score: u32,
+}
+
+impl Bird {
+    fn new() -> Bird {
+        Bird {
+            y: HEIGHT / 2.0,
+            velocity: 0.0,
+            alive: true,
+            score: 0,
+        }
+    }
+}
+
+/// One frame of Flappy Bird: bird heights (`None` once down) and pipes as `(x, gap_y)`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlappyView {
+    pub birds: Vec<Option<f64>>,
+    pub pipes: Vec<(f64, f64)>,
+    pub pipe_gap: f64,
+    pub hud: Hud,
+}
+
+/// The Flappy Bird rules without a canvas. `FlappyBird` draws it and reacts to its events.
+pub struct FlappySim {
+    birds: Vec<Bird>, // ✅ One per player, racing the same pipes
     pipes: Vec<(f64, f64)>,
-    context: CanvasRenderingContext2d,
+    unsimulated: f64, // ✅ Seconds passed to `update` not yet run as a substep
+    previous: (Vec<f64>, Vec<(f64, f64)>), // ✅ Bird heights and pipes a tick ago, for `motion`
     running: bool,
+    config: FlappyConfig,
+    game_config: GameConfig,
+    rng: GameRng,
+    seed: u64,
+    last_run: Option<(u64, u32)>, // ✅ (seed, score) of the last finished solo run
+    last_result: Option<RunResult>,
+    events: Vec<GameEvent>,
 }
 
-#[wasm_bindgen]
-impl FlappyBird {
-    #[wasm_bindgen(constructor)]
-    pub fn new(canvas: HtmlCanvasElement) -> FlappyBird {
-        let context = canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()
-            .unwrap();
+impl FlappySim {
+    pub fn new(config: &GameConfig) -> FlappySim {
+        let seed = rng::run_seed(config);
 
-        FlappyBird {
-            bird_y: HEIGHT / 2.0,
-            bird_velocity: 0.0,
+        let mut sim = FlappySim {
+            birds: (0..config.players()).map(|_| Bird::new()).collect(),
             pipes: vec![(WIDTH, 200.0)],
-            context,
+            unsimulated: 0.0,
+            previous: (Vec::new(), Vec::new()),
             running: false,
-        }
+            config: config.flappy(),
+            game_config: config.clone(),
+            rng: rng::seeded(seed),
+            seed,
+            last_run: None,
+            last_result: None,
+            events: Vec::new(),
+        };
+        sim.previous = sim.positions();
+        sim
     }
 
-    #[wasm_bindgen]
     pub fn update(&mut self, delta_time: f64) {
+        self.previous = self.positions();
         if !self.running {
             return;
         }
-    
-        let time_scale = delta_time * 1000.0 / 16.67;  // ✅ Normalize updates to 60 FPS
-    
-        // ✅ Adjust gravity effect based on time scaling
-        self.bird_velocity += GRAVITY * time_scale;
-        if self.bird_velocity > 10.0 {
-            self.bird_velocity = 10.0; // ✅ Clamp fall speed
-        }
-        self.bird_y += self.bird_velocity * time_scale;
-    
-        // ✅ Move pipes left based on deltaTime
-        let pipe_speed = 2.0 * time_scale; // ✅ Speed up pipe movement
+
+        self.unsimulated += delta_time;
+        while self.running && self.unsimulated >= SUBSTEP - SUBSTEP_TOLERANCE {
+            self.unsimulated -= SUBSTEP;
+            self.step(SUBSTEP);
+        }
+    }
+
+    /// Runs `dt` seconds of physics, then scoring and collisions.
+    fn step(&mut self, dt: f64) {
+        for bird in self.birds.iter_mut().filter(|bird| bird.alive) {
+            let velocity = (bird.velocity + self.config.gravity * dt).min(self.config.max_fall_speed);
+            bird.y += (bird.velocity + velocity) / 2.0 * dt; // ✅ Exact under constant gravity
+            bird.velocity = velocity;
+        }
+
+        let pipe_speed = self.config.pipe_speed * dt;
+        let mut cleared = 0;
         for pipe in &mut self.pipes {
+            let was_ahead = pipe.0 + PIPE_WIDTH >= BIRD_LEFT;
             pipe.0 -= pipe_speed;
+            if was_ahead && pipe.0 + PIPE_WIDTH < BIRD_LEFT {
+                cleared += 1; // ✅ Pipe cleared
+            }
+        }
+        for _ in 0..cleared {
+            for bird in self.birds.iter_mut().filter(|bird| bird.alive) {
+                bird.score += 1;
+                self.events.push(GameEvent::PipePassed { total: bird.score });
+            }
         }
-    
+
         // ✅ Generate new pipes with correct spacing
         if let Some(&(last_x, _)) = self.pipes.last() {
-            if last_x < WIDTH - 250.0 { // ✅ Increased spacing to 250px
-                let gap_y = (js_sys::Math::random() * (HEIGHT - PIPE_GAP)) as f64;
+            if last_x < WIDTH - self.config.pipe_spacing { // ✅ Keep pipes `pipe_spacing` apart
+                let gap_y = self.rng.gen::<f64>() * (HEIGHT - self.config.pipe_gap);
                 self.pipes.push((WIDTH, gap_y));
             }
         }
-    
+
         // ✅ Remove offscreen pipes
         self.pipes.retain(|&(x, _)| x > -PIPE_WIDTH);
-    
+
         // ✅ Collision detection
-        let mut collision = false;
-        for &(pipe_x, gap_y) in &self.pipes {
-            if (pipe_x < 50.0 && pipe_x + PIPE_WIDTH > 30.0) &&
-               (self.bird_y - BIRD_RADIUS / 2.0 < gap_y || self.bird_y + BIRD_RADIUS / 2.0 > gap_y + PIPE_GAP) {
-                collision = true;
-                break;
+        let mut just_died = Vec::new();
+        for (i, bird) in self.birds.iter_mut().enumerate().filter(|(_, bird)| bird.alive) {
+            let mut collision = None;
+            for &(pipe_x, gap_y) in &self.pipes {
+                if (pipe_x < 50.0 && pipe_x + PIPE_WIDTH > BIRD_LEFT) &&
+                   (bird.y - BIRD_RADIUS / 2.0 < gap_y || bird.y + BIRD_RADIUS / 2.0 > gap_y + self.config.pipe_gap) {
+                    collision = Some(DeathCause::Pipe);
+                    break;
+                }
+            }
+
+            // ✅ Check for ground and ceiling collision
+            if bird.y < 0.0 {
+                collision = Some(DeathCause::Ceiling);
+            } else if bird.y > HEIGHT {
+                collision = Some(DeathCause::Ground);
+            }
+
+            if let Some(cause) = collision {
+                bird.alive = false;
+                just_died.push(i);
+                self.events.push(GameEvent::Died { player: i, cause });
             }
         }
-    
-        // ✅ Check for ground and ceiling collision
-        if self.bird_y < 0.0 || self.bird_y > HEIGHT {
-            collision = true;
+
+        // ✅ Reset once every bird is down; the last one flying wins
+        if self.birds.iter().all(|bird| !bird.alive) {
+            let winner = if just_died.len() == 1 { Some(just_died[0]) } else { None };
+            self.game_over(winner);
         }
-    
-        // ✅ Reset if collision detected
-        if collision {
-            self.reset();
+    }
+
+    /// Flaps player `player`'s bird (0 or 1).
+    pub fn flap(&mut self, player: usize) {
+        if player >= self.birds.len() {
+            return;
         }
-    }    
+        if !self.running {
+            self.running = true;
+            self.events.push(GameEvent::Started);
+        }
+        if self.birds[player].alive {
+            self.birds[player].velocity = self.config.jump_strength;
+        }
+    }
 
-    #[wasm_bindgen]
-    pub fn render(&self) {
-        self.context.set_fill_style(&"black".into());
-        self.context.fill_rect(0.0, 0.0, WIDTH, HEIGHT);
+    pub fn reset(&mut self) {
+        self.birds = (0..self.game_config.players()).map(|_| Bird::new()).collect();
+        self.pipes = vec![(WIDTH, 200.0)];
+        self.unsimulated = 0.0;
+        self.previous = self.positions();
+        self.running = false;
+        self.seed = rng::run_seed(&self.game_config);
+        self.rng = rng::seeded(self.seed);
+    }
+
+    /// No run going: before the first input and after a game over.
+    pub fn waiting(&self) -> bool {
+        !self.running
+    }
+
+    /// Every player's score, in player order.
+    pub fn scores(&self) -> Vec<u32> {
+        self.birds.iter().map(|bird| bird.score).collect()
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    pub fn challenge_token(&self) -> Option<String> {
+        self.last_run
+            .map(|(seed, score)| challenge::encode(&self.game_config, seed, score))
+    }
+
+    /// Events since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn view(&self) -> FlappyView {
+        FlappyView {
+            birds: self.birds.iter().map(|bird| bird.alive.then_some(view::round(bird.y))).collect(),
+            pipes: self.pipes.iter().map(|&(x, gap_y)| (view::round(x), view::round(gap_y))).collect(),
+            pipe_gap: self.config.pipe_gap,
+            hud: Hud {
+                scores: self.scores(),
+                result: if self.running { None } else { self.last_result },
+                challenge_score: self.game_config.challenge_score,
+            },
+        }
+    }
+
+    /// Where the birds and pipes were a tick ago, for a frame drawn `alpha`
+    /// of a tick after the last update.
+    pub(crate) fn motion(&self, alpha: f64) -> FlappyMotion {
+        let (birds, pipes) = self.previous.clone();
+        FlappyMotion { birds, pipes, alpha }
+    }
+
+    fn positions(&self) -> (Vec<f64>, Vec<(f64, f64)>) {
+        (self.birds.iter().map(|bird| bird.y).collect(), self.pipes.clone())
+    }
+
+    /// Player 1's view for `env.rs`. Features: bird height and speed, then
+    /// distance to and gap of the next two pipes. Grid: pipes and bird at
+    /// 20×20 pixels per cell.
+    pub(crate) fn observe(&self, kind: ObservationKind) -> Observation {
+        let bird = &self.birds[0];
+        let ahead = self.pipes.iter().filter(|&&(x, _)| x + PIPE_WIDTH >= BIRD_LEFT);
+
+        match kind {
+            ObservationKind::Features => {
+                let mut features = vec![(bird.y / HEIGHT) as f32, (bird.velocity / self.config.max_fall_speed) as f32];
+                let mut pipes = ahead.map(|&(x, gap_y)| (x - BIRD_LEFT, gap_y));
+                for _ in 0..2 {
+                    let (dx, gap_y) = pipes.next().unwrap_or((WIDTH, (HEIGHT - self.config.pipe_gap) / 2.0));
+                    features.extend([dx / WIDTH, gap_y / HEIGHT, (gap_y + self.config.pipe_gap) / HEIGHT].map(|v| v as f32));
+                }
+                Observation::features(features)
+            }
+            ObservationKind::Grid => {
+                const CELL: f64 = 20.0;
+                let (width, height) = ((WIDTH / CELL) as usize, (HEIGHT / CELL) as usize);
+                let mut grid = Observation::grid(2, width, height);
+                for &(pipe_x, gap_y) in &self.pipes {
+                    for y in 0..height {
+                        let center = (y as f64 + 0.5) * CELL;
+                        if center >= gap_y && center <= gap_y + self.config.pipe_gap {
+                            continue;
+                        }
+                        let first = (pipe_x / CELL).floor().max(0.0) as usize;
+                        let last = ((pipe_x + PIPE_WIDTH) / CELL).ceil().max(0.0) as usize;
+                        for x in first..last {
+                            grid.set(0, x, y);
+                        }
+                    }
+                }
+                if bird.alive && bird.y >= 0.0 {
+                    grid.set(1, (50.0 / CELL) as usize, (bird.y / CELL) as usize);
+                }
+                grid
+            }
+        }
+    }
+
+    fn game_over(&mut self, winner: Option<usize>) {
+        let score = self.birds[0].score;
+        if self.birds.len() == 1 {
+            self.last_run = Some((self.seed, score));
+            self.last_result = Some(RunResult::Score(score));
+        } else {
+            self.last_result = Some(RunResult::Winner(winner));
+        }
+        self.events.push(GameEvent::GameOver { score });
+        self.reset();
+    }
+}
+
+/// What `draw` needs to draw between ticks.
+pub(crate) struct FlappyMotion {
+    birds: Vec<f64>,
+    pipes: Vec<(f64, f64)>,
+    alpha: f64,
+}
 
-        self.context.set_fill_style(&"yellow".into());
-        self.context.begin_path();
-        self.context.arc(50.0, self.bird_y, BIRD_RADIUS, 0.0, f64::consts::PI * 2.0).unwrap();
-        self.context.fill();
+/// Draws `view`, or `motion.alpha` of the way there from the tick before
+/// when there's a `motion`.
+pub(crate) fn draw(context: &dyn Canvas, view: &FlappyView, motion: Option<&FlappyMotion>, style: &Style) {
+    let palette = style.palette();
+    context.set_fill_style_str(palette.background);
+    context.fill_rect(0.0, 0.0, WIDTH, HEIGHT);
 
-        self.context.set_fill_style(&"green".into());
-        for &(pipe_x, gap_y) in &self.pipes {
-            self.context.fill_rect(pipe_x, 0.0, PIPE_WIDTH, gap_y);
-            self.context.fill_rect(pipe_x, gap_y + PIPE_GAP, PIPE_WIDTH, HEIGHT - gap_y - PIPE_GAP);
+    for (i, (bird, color)) in view.birds.iter().zip(palette.birds).enumerate() {
+        if let Some(y) = *bird {
+            let y = match motion.and_then(|motion| Some((*motion.birds.get(i)?, motion.alpha))) {
+                Some((from, alpha)) => view::lerp(from, y, alpha),
+                None => y,
+            };
+            context.set_fill_style_str(color);
+            context.begin_path();
+            context.arc(50.0, y, BIRD_RADIUS, 0.0, f64::consts::PI * 2.0).unwrap();
+            context.fill();
+            if style.cues && view.birds.len() > 1 {
+                game::draw_cue(context, &(i + 1).to_string(), (50.0, y), 20.0, palette.background);
+            }
         }
     }
 
+    context.set_fill_style_str(palette.pipes);
+    for &(pipe_x, gap_y) in &view.pipes {
+        // ✅ A pipe is the one with the same gap a tick ago; new pipes have none
+        let from = motion.and_then(|motion| {
+            let &(from_x, _) = motion.pipes.iter().find(|&&(_, from_gap)| view::round(from_gap) == gap_y)?;
+            Some((from_x, motion.alpha))
+        });
+        let pipe_x = match from {
+            Some((from_x, alpha)) => view::lerp(from_x, pipe_x, alpha),
+            None => pipe_x,
+        };
+        context.fill_rect(pipe_x, 0.0, PIPE_WIDTH, gap_y);
+        let gap_bottom = gap_y + view.pipe_gap;
+        context.fill_rect(pipe_x, gap_bottom, PIPE_WIDTH, HEIGHT - gap_bottom);
+    }
+
+    game::draw_hud(context, WIDTH, HEIGHT, &view.hud, style);
+}
+
+#[wasm_bindgen]
+pub struct FlappyBird {
+    sim: FlappySim,
+    context: CanvasRenderingContext2d,
+    achievements: Achievements,
+    announcer: Announcer,
+    recorder: Recorder,
+    style: Style,
+}
+
+#[wasm_bindgen]
+impl FlappyBird {
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas: HtmlCanvasElement) -> FlappyBird {
+        Self::with_config(canvas, &GameConfig::new(GameKind::Flappy))
+    }
+
     #[wasm_bindgen]
-    pub fn flap(&mut self) {
-        if !self.running {
-            self.running = true;
+    pub fn with_config(canvas: HtmlCanvasElement, config: &GameConfig) -> FlappyBird {
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+
+        FlappyBird {
+            sim: FlappySim::new(config),
+            context,
+            achievements: Achievements::load(),
+            announcer: Announcer::new(GameKind::Flappy),
+            recorder: Recorder::new(),
+            style: config.style(),
         }
-        self.bird_velocity = JUMP_STRENGTH;
+    }
+
+    #[wasm_bindgen]
+    pub fn update(&mut self, delta_time: f64) {
+        self.achievements.update(delta_time);
+        self.sim.update(delta_time);
+        self.record_events();
+        self.recorder.tick();
+    }
+
+    #[wasm_bindgen]
+    pub fn render(&self) {
+        self.draw_frame(None);
+    }
+
+    /// Draws the birds and pipes `alpha` (0 to 1) of a tick past the last
+    /// update, so motion stays smooth on displays faster than the tick rate.
+    #[wasm_bindgen]
+    pub fn render_at(&self, alpha: f64) {
+        self.draw_frame(Some(&self.sim.motion(alpha)));
+    }
+
+    #[wasm_bindgen]
+    pub fn flap(&mut self) {
+        self.flap_player(0);
+    }
+
+    /// Flaps player `player`'s bird (0 or 1).
+    #[wasm_bindgen]
+    pub fn flap_player(&mut self, player: usize) {
+        self.command(Command::Flap(player));
     }
 
     #[wasm_bindgen]  // <-- Add this to expose `reset()`
     pub fn reset(&mut self) {
-        self.bird_y = HEIGHT / 2.0;
-        self.bird_velocity = 0.0;
-        self.pipes = vec![(WIDTH, 200.0)];
-        self.running = false;
+        self.sim.reset();
+        self.recorder.cancel();
+    }
+
+    /// Player 1's score.
+    #[wasm_bindgen]
+    pub fn score(&self) -> u32 {
+        self.sim.scores()[0]
     }
-}
\ No newline at end of file
+
+    /// Every player's score, in player order.
+    #[wasm_bindgen]
+    pub fn scores(&self) -> Vec<u32> {
+        self.sim.scores()
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    #[wasm_bindgen]
+    pub fn challenge_token(&self) -> Option<String> {
+        self.sim.challenge_token()
+    }
+
+    /// JSON input replay of the last finished solo run, for the leaderboard.
+    #[wasm_bindgen]
+    pub fn replay(&self) -> Option<String> {
+        self.recorder.last().map(|replay| serde_json::to_string(replay).expect("replays serialize"))
+    }
+
+    /// Solo runs finished so far; when it changes there is a new challenge
+    /// token and replay to offer.
+    #[wasm_bindgen]
+    pub fn finished_runs(&self) -> u32 {
+        self.recorder.finished()
+    }
+}
+
+impl FlappyBird {
+    pub(crate) fn view(&self) -> View {
+        View::Flappy(self.sim.view())
+    }
+
+    pub(crate) fn waiting(&self) -> bool {
+        self.sim.waiting()
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Whether a bot is playing player 1, whose runs unlock no achievements.
+    pub(crate) fn set_bot(&mut self, bot: bool) {
+        self.achievements.set_bot(bot);
+    }
+
+    fn draw_frame(&self, motion: Option<&FlappyMotion>) {
+        draw(&self.context, &self.sim.view(), motion, &self.style);
+        self.achievements.render(&self.context, WIDTH, HEIGHT, &self.style);
+    }
+
+    fn record_events(&mut self) {
+        let events = self.sim.take_events();
+        self.recorder.events(&events, &self.sim);
+        for event in &events {
+            self.achievements.record(event);
+        }
+        self.announcer.update(&events, &self.sim, self.style.strings());
+    }
+
+    pub(crate) fn command(&mut self, command: Command) {
+        Sim::command(&mut self.sim, command);
+        self.record_events();
+        self.recorder.command(command);
+    }
+}
@@ -0,0 +1,603 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use crate::agent::{self, Agent, JsAgent, AGENTS};
+use crate::attract::Attract;
+use crate::broadcast::{Broadcaster, Spectator};
+use crate::config::{self, ConfigError, GameConfig};
+use crate::draw::Canvas;
+use crate::gamepad::Gamepads;
+use crate::input::{self, Action, HeldDirections};
+use crate::locale::{fill, Language, Strings};
+use crate::online::OnlineSnake;
+use crate::theme::{Style, Theme};
+use crate::touch::Touch;
+use crate::view::{Hud, View};
+use crate::{flappy, pacman, snake};
+use crate::{FlappyBird, PacmanGame, SnakeGame};
+
+/// The games this crate knows how to run, as named in `?game=` and `<wasm-game name="...">`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameKind {
+    Flappy,
+    Snake,
+    Pacman,
+}
+
+impl GameKind {
+    pub fn from_name(name: &str) -> Option<GameKind> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "flappy" => Some(GameKind::Flappy),
+            "snake" => Some(GameKind::Snake),
+            "pacman" => Some(GameKind::Pacman),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameKind::Flappy => "flappy",
+            GameKind::Snake => "snake",
+            GameKind::Pacman => "pacman",
+        }
+    }
+
+    /// Canvas size in pixels the game renders into with its default settings.
+    pub fn canvas_size(self) -> (u32, u32) {
+        match self {
+            GameKind::Flappy => (flappy::WIDTH as u32, flappy::HEIGHT as u32),
+            GameKind::Snake => (
+                (snake::WIDTH as f64 * snake::GRID_SIZE) as u32,
+                (snake::HEIGHT as f64 * snake::GRID_SIZE) as u32,
+            ),
+            GameKind::Pacman => (
+                (pacman::WIDTH as f64 * pacman::GRID_SIZE) as u32,
+                (pacman::HEIGHT as f64 * pacman::GRID_SIZE) as u32,
+            ),
+        }
+    }
+}
+
+enum Inner {
+    Flappy(FlappyBird),
+    Snake(SnakeGame),
+    Pacman(Box<PacmanGame>),
+    Online(Box<OnlineSnake>),
+    Watch(Box<Spectator>),
+}
+
+impl Inner {
+    fn view(&self) -> Option<View> {
+        match self {
+            Inner::Flappy(game) => Some(game.view()),
+            Inner::Snake(game) => Some(game.view()),
+            Inner::Pacman(game) => Some(game.view()),
+            Inner::Online(game) => game.view(),
+            Inner::Watch(_) => None,
+        }
+    }
+
+    /// On the title screen of an offline game, waiting for a run to start.
+    fn waiting(&self) -> bool {
+        match self {
+            Inner::Flappy(game) => game.waiting(),
+            Inner::Snake(game) => game.waiting(),
+            Inner::Pacman(game) => game.waiting(),
+            Inner::Online(_) | Inner::Watch(_) => false,
+        }
+    }
+
+    fn set_bot(&mut self, bot: bool) {
+        match self {
+            Inner::Flappy(game) => game.set_bot(bot),
+            Inner::Snake(game) => game.set_bot(bot),
+            Inner::Pacman(game) => game.set_bot(bot),
+            Inner::Online(_) | Inner::Watch(_) => {}
+        }
+    }
+}
+
+/// Any one of the games behind the shared input layer, so hosts (`index.html`,
+/// `<wasm-game>`) just forward keyboard and pointer events; gamepads are
+/// polled on each `update`.
+#[wasm_bindgen]
+pub struct Game {
+    inner: Inner,
+    players: usize,
+    held: [HeldDirections; 2],
+    broadcaster: Option<Broadcaster>,
+    /// Bot playing player 1's seat in place of the keyboard.
+    agent: Option<Box<dyn Agent>>,
+    attract: Option<Attract>,
+    /// Where the attract demo draws, over the game's own frame.
+    context: CanvasRenderingContext2d,
+    touch: Touch,
+    gamepads: Gamepads,
+    style: Style,
+}
+
+#[wasm_bindgen]
+impl Game {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &GameConfig, canvas: HtmlCanvasElement) -> Result<Game, JsValue> {
+        let attract = Attract::new(config);
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+        let touch = Touch::new(config.touch(), config.game() == GameKind::Flappy, &canvas);
+        let gamepads = Gamepads::new(&canvas);
+        let inner = match config.game() {
+            _ if config.watch().is_some() => {
+                Inner::Watch(Box::new(Spectator::new(config.watch().unwrap(), config.style(), canvas)?))
+            }
+            _ if config.online().is_some() => Inner::Online(Box::new(OnlineSnake::new(config, canvas)?)),
+            GameKind::Flappy => Inner::Flappy(FlappyBird::with_config(canvas, config)),
+            GameKind::Snake => Inner::Snake(SnakeGame::with_config(canvas, config)),
+            GameKind::Pacman => Inner::Pacman(Box::new(PacmanGame::with_config(canvas, config))),
+        };
+
+        Ok(Game {
+            inner,
+            players: config.players(),
+            held: Default::default(),
+            broadcaster: config.spectate().map(Broadcaster::open).transpose()?,
+            agent: None,
+            attract,
+            context,
+            touch,
+            gamepads,
+            style: config.style(),
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn update(&mut self, delta_time: f64) {
+        self.step(delta_time, true);
+    }
+
+    /// Draws the latest tick as it is.
+    #[wasm_bindgen]
+    pub fn render(&self) {
+        self.draw(None);
+    }
+
+    /// Draws the frame `alpha` (0 to 1) of a fixed step after the last
+    /// `update`, i.e. the loop's leftover time over `FIXED_TIMESTEP`, so local
+    /// games move smoothly between ticks. The attract demo, online games and
+    /// spectating draw the latest tick as `render` does.
+    #[wasm_bindgen]
+    pub fn render_at(&self, alpha: f64) {
+        self.draw(Some(alpha));
+    }
+
+    /// Handles a `keydown`; returns whether the key was used by the game.
+    #[wasm_bindgen]
+    pub fn key_down(&mut self, key: &str, code: &str) -> bool {
+        if self.attract.as_mut().is_some_and(Attract::input) {
+            return true; // ✅ Back to the title; the next key starts a run
+        }
+        match self.binding(key, code) {
+            Some((player, action)) => self.press(player, action),
+            None => false,
+        }
+    }
+
+    /// Handles a `keyup`; returns whether the key was used by the game.
+    #[wasm_bindgen]
+    pub fn key_up(&mut self, key: &str, code: &str) -> bool {
+        match self.binding(key, code) {
+            Some((player, action)) => self.release(player, action),
+            None => false,
+        }
+    }
+
+    /// Handles a `pointerdown` at `(x, y)` in canvas pixels: a tap flaps, and
+    /// in the grid games it starts a swipe or presses the D-pad. Hosts should
+    /// listen for pointer events only, not mouse or touch events as well,
+    /// or a tap counts twice.
+    #[wasm_bindgen]
+    pub fn pointer_down(&mut self, pointer_id: i32, x: f64, y: f64) {
+        if self.attract.as_mut().is_some_and(Attract::input) {
+            return;
+        }
+        if matches!(self.inner, Inner::Watch(_)) {
+            return;
+        }
+        if let Some(action) = self.touch.down(pointer_id, (x, y)) {
+            self.press(0, action);
+        }
+    }
+
+    /// Handles a `pointermove`: a swipe steers once it's long enough.
+    #[wasm_bindgen]
+    pub fn pointer_move(&mut self, pointer_id: i32, x: f64, y: f64) {
+        if let Some((action, released)) = self.touch.moved(pointer_id, (x, y)) {
+            if let Some(released) = released {
+                self.release(0, released);
+            }
+            self.press(0, action);
+        }
+    }
+
+    /// Handles a `pointerup` or `pointercancel`.
+    #[wasm_bindgen]
+    pub fn pointer_up(&mut self, pointer_id: i32) {
+        if let Some(released) = self.touch.up(pointer_id) {
+            self.release(0, released);
+        }
+    }
+
+    /// Shows or hides the on-screen D-pad in the grid games.
+    #[wasm_bindgen]
+    pub fn set_dpad(&mut self, dpad: bool) {
+        self.touch.set_dpad(dpad);
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished solo run.
+    #[wasm_bindgen]
+    pub fn challenge_token(&self) -> Option<String> {
+        match &self.inner {
+            Inner::Flappy(game) => game.challenge_token(),
+            Inner::Snake(game) => game.challenge_token(),
+            Inner::Pacman(game) => game.challenge_token(),
+            Inner::Online(_) | Inner::Watch(_) => None,
+        }
+    }
+
+    /// JSON input replay of the last finished solo run, for the leaderboard.
+    #[wasm_bindgen]
+    pub fn replay(&self) -> Option<String> {
+        match &self.inner {
+            Inner::Flappy(game) => game.replay(),
+            Inner::Snake(game) => game.replay(),
+            Inner::Pacman(game) => game.replay(),
+            Inner::Online(_) | Inner::Watch(_) => None,
+        }
+    }
+
+    /// Solo runs finished so far, cheap enough to check every frame: when it
+    /// changes, `challenge_token` and `replay` have a new run to offer.
+    #[wasm_bindgen]
+    pub fn finished_runs(&self) -> u32 {
+        match &self.inner {
+            Inner::Flappy(game) => game.finished_runs(),
+            Inner::Snake(game) => game.finished_runs(),
+            Inner::Pacman(game) => game.finished_runs(),
+            Inner::Online(_) | Inner::Watch(_) => 0,
+        }
+    }
+
+    /// Hands player 1 to a built-in bot (one of `agents()`) from the next
+    /// update; `None` takes the bot off again. Offline games only. Runs a
+    /// bot plays any of unlock no achievements.
+    #[wasm_bindgen]
+    pub fn set_agent(&mut self, name: Option<String>) -> Result<(), ConfigError> {
+        self.agent = match name {
+            Some(name) => Some(agent::agent(&name, rand::random()).ok_or(ConfigError::Invalid {
+                param: "agent",
+                value: name,
+                expected: agent::agent_names(),
+            })?),
+            None => None,
+        };
+        self.inner.set_bot(self.agent.is_some());
+        Ok(())
+    }
+
+    /// Hands player 1 to a JS function, called before every update with the
+    /// frame as `View` JSON and returning a `Command` as JSON, e.g.
+    /// `{"flap":0}` or `{"steer":[0,1,0]}`, or nothing to wait.
+    #[wasm_bindgen]
+    pub fn set_agent_callback(&mut self, callback: js_sys::Function) {
+        self.agent = Some(Box::new(JsAgent(callback)));
+        self.inner.set_bot(true);
+    }
+
+    /// Names `set_agent` accepts.
+    #[wasm_bindgen]
+    pub fn agents() -> Vec<String> {
+        AGENTS.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// Switches colour theme from the next frame, e.g. from a settings menu.
+    #[wasm_bindgen]
+    pub fn set_theme(&mut self, name: String) -> Result<(), ConfigError> {
+        let style = Style { theme: config::parse_theme(name)?, ..self.style };
+        self.set_style(style);
+        Ok(())
+    }
+
+    /// Turns shape cues (ghost letters, player numbers, outlined food) on or off.
+    #[wasm_bindgen]
+    pub fn set_cues(&mut self, cues: bool) {
+        self.set_style(Style { cues, ..self.style });
+    }
+
+    /// Reduced motion: no flashing, and no attract demo starting by itself.
+    /// Hosts default it from `prefers-reduced-motion`.
+    #[wasm_bindgen]
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.set_style(Style { reduced_motion, ..self.style });
+    }
+
+    /// Switches the language of the game's text from the next frame. Takes
+    /// language tags in order of preference (`"pt-BR,en"`), falling back to
+    /// English, and returns the code of the language picked.
+    #[wasm_bindgen]
+    pub fn set_language(&mut self, tags: String) -> String {
+        let language = Language::negotiate(&tags);
+        self.set_style(Style { language, ..self.style });
+        language.code().to_string()
+    }
+
+    /// Codes of the bundled languages.
+    #[wasm_bindgen]
+    pub fn languages() -> Vec<String> {
+        Language::ALL.iter().map(|language| language.code().to_string()).collect()
+    }
+
+    /// The current language's text as a plain JS object, for the page
+    /// around the canvas; fill in `{}` where a template has one.
+    #[wasm_bindgen]
+    pub fn strings(&self) -> Result<JsValue, JsValue> {
+        let json = serde_json::to_string(self.style.strings()).map_err(|err| JsError::new(&err.to_string()))?;
+        js_sys::JSON::parse(&json)
+    }
+
+    /// Names `set_theme` accepts.
+    #[wasm_bindgen]
+    pub fn themes() -> Vec<String> {
+        Theme::ALL.iter().map(|theme| theme.name().to_string()).collect()
+    }
+
+    /// The current theme's colours as a plain JS object, so the page around
+    /// the canvas can match (`page`, `border`).
+    #[wasm_bindgen]
+    pub fn palette(&self) -> Result<JsValue, JsValue> {
+        let json = serde_json::to_string(self.style.palette()).map_err(|err| JsError::new(&err.to_string()))?;
+        js_sys::JSON::parse(&json)
+    }
+
+    /// `player` presses `action`'s key or button; returns whether the game
+    /// uses it.
+    fn press(&mut self, player: usize, action: Action) -> bool {
+        match &mut self.inner {
+            Inner::Flappy(game) => match action {
+                Action::Flap | Action::Up => game.flap_player(player),
+                _ => return false,
+            },
+            Inner::Snake(game) => match action.direction() {
+                Some((dx, dy)) => game.change_player_direction(player, dx, dy),
+                None => return false,
+            },
+            Inner::Pacman(game) => {
+                self.held[player].set(action, true);
+                steer(game, player, self.held[player].direction());
+            }
+            Inner::Online(game) => match action.direction() {
+                Some((dx, dy)) => game.change_direction(dx, dy), // ✅ Either set of keys steers your own snake
+                None => return false,
+            },
+            Inner::Watch(_) => return false,
+        }
+        true
+    }
+
+    /// `player` lets go of `action`; only Pac-Man cares.
+    fn release(&mut self, player: usize, action: Action) -> bool {
+        match &mut self.inner {
+            Inner::Pacman(game) => {
+                self.held[player].set(action, false);
+                steer(game, player, self.held[player].direction());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Key binding, ignoring player 2's keys in a one-player game.
+    fn binding(&self, key: &str, code: &str) -> Option<(usize, Action)> {
+        input::key_binding(key, code).filter(|&(player, _)| player < self.players)
+    }
+}
+
+impl Game {
+    /// One fixed update; `gamepads` says whether this game reads the pads,
+    /// which a page with several `<wasm-game>`s gives only to the last focused.
+    pub(crate) fn step(&mut self, delta_time: f64, gamepads: bool) {
+        if gamepads {
+            self.poll_gamepads();
+        }
+        self.drive();
+
+        if let Some(attract) = &mut self.attract {
+            attract.update(delta_time, self.agent.is_none() && self.inner.waiting());
+            if attract.active() {
+                return; // ✅ The game stays on its title behind the demo
+            }
+        }
+
+        match &mut self.inner {
+            Inner::Flappy(game) => game.update(delta_time),
+            Inner::Snake(game) => game.update(delta_time),
+            Inner::Pacman(game) => game.update(delta_time),
+            Inner::Online(game) => game.update(), // ✅ One lockstep tick per fixed step
+            Inner::Watch(spectator) => spectator.update(),
+        }
+
+        if let (Some(broadcaster), Some(view)) = (&mut self.broadcaster, self.inner.view()) {
+            broadcaster.publish(&view);
+        }
+    }
+
+    /// Seconds idle on the title before the attract demo, if it's on and
+    /// may start by itself.
+    pub(crate) fn attract_delay(&self) -> Option<f64> {
+        self.attract.as_ref().filter(|_| !self.style.reduced_motion).map(Attract::delay)
+    }
+
+    /// Starts the attract demo now, for hosts that only run the loop once
+    /// the delay is up.
+    pub(crate) fn start_attract(&mut self) {
+        if let Some(attract) = &mut self.attract {
+            attract.start();
+        }
+    }
+
+    /// `alpha` of a tick on from the latest, when given and the game can.
+    fn draw(&self, alpha: Option<f64>) {
+        if let Some(attract) = self.attract.as_ref().filter(|attract| attract.active()) {
+            attract.render(&self.context);
+            return;
+        }
+
+        match (&self.inner, alpha) {
+            (Inner::Flappy(game), Some(alpha)) => game.render_at(alpha),
+            (Inner::Snake(game), Some(alpha)) => game.render_at(alpha),
+            (Inner::Pacman(game), Some(alpha)) => game.render_at(alpha),
+            (Inner::Flappy(game), None) => game.render(),
+            (Inner::Snake(game), None) => game.render(),
+            (Inner::Pacman(game), None) => game.render(),
+            (Inner::Online(game), _) => game.render(),
+            (Inner::Watch(spectator), _) => spectator.render(),
+        }
+        if matches!(self.inner, Inner::Snake(_) | Inner::Pacman(_) | Inner::Online(_)) {
+            self.touch.render(&self.style);
+        }
+        if self.inner.waiting() {
+            self.gamepads.render_prompt(&self.style, matches!(self.inner, Inner::Flappy(_)));
+        }
+    }
+
+    fn set_style(&mut self, style: Style) {
+        self.style = style;
+        if let Some(attract) = &mut self.attract {
+            attract.set_style(style);
+        }
+        match &mut self.inner {
+            Inner::Flappy(game) => game.set_style(style),
+            Inner::Snake(game) => game.set_style(style),
+            Inner::Pacman(game) => game.set_style(style),
+            Inner::Online(game) => game.set_style(style),
+            Inner::Watch(spectator) => spectator.set_style(style),
+        }
+    }
+
+    /// Applies what the gamepads pressed and let go of since the last tick,
+    /// ignoring player 2's pad in a one-player game.
+    fn poll_gamepads(&mut self) {
+        for (player, action, pressed) in self.gamepads.poll() {
+            if player >= self.players {
+                continue;
+            }
+            if !pressed {
+                self.release(player, action);
+            } else if !self.attract.as_mut().is_some_and(Attract::input) {
+                self.press(player, action);
+            }
+        }
+    }
+
+    /// Lets the agent, if any, act on the current frame.
+    fn drive(&mut self) {
+        let Some(agent) = &mut self.agent else {
+            return;
+        };
+        let Some(view) = self.inner.view() else {
+            return;
+        };
+        let Some(command) = agent.act(&view) else {
+            return;
+        };
+
+        match &mut self.inner {
+            Inner::Flappy(game) => game.command(command),
+            Inner::Snake(game) => game.command(command),
+            Inner::Pacman(game) => game.command(command),
+            Inner::Online(_) | Inner::Watch(_) => {}
+        }
+    }
+}
+
+/// ✅ Pac-Man for player 1, the controlled ghost for player 2
+fn steer(game: &mut PacmanGame, player: usize, (dx, dy): (i32, i32)) {
+    if player == 0 {
+        game.change_direction(dx, dy);
+    } else {
+        game.change_ghost_direction(dx, dy);
+    }
+}
+
+/// How the last run ended, shown over the still frame until the next one starts.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunResult {
+    Score(u32),
+    /// Two-player game: the winning player, `None` for a draw.
+    Winner(Option<usize>),
+}
+
+/// A shape cue: `label` centred on `(x, y)` in `size`px bold type, for things
+/// otherwise told apart only by colour.
+pub(crate) fn draw_cue(context: &dyn Canvas, label: &str, (x, y): (f64, f64), size: f64, color: &str) {
+    context.set_fill_style_str(color);
+    context.set_font(&format!("bold {}px monospace", size));
+    context.set_text_align("center");
+    context.fill_text(label, x, y + size * 0.35).unwrap();
+}
+
+/// Scores along the top, the score to beat on a challenge, and the result of
+/// the last run while the game waits to be restarted.
+pub(crate) fn draw_hud(context: &dyn Canvas, width: f64, height: f64, hud: &Hud, style: &Style) {
+    let strings = style.strings();
+    context.set_fill_style_str(style.palette().text);
+    context.set_font("16px monospace");
+    context.set_text_align("left");
+
+    if let [score] = hud.scores[..] {
+        context.fill_text(&fill(strings.score, &[&score]), 8.0, 20.0).unwrap();
+    } else {
+        for (i, score) in hud.scores.iter().enumerate() {
+            let x = 8.0 + i as f64 * (width - 16.0) / 2.0;
+            context.fill_text(&fill(strings.player_score, &[&(i + 1), score]), x, 20.0).unwrap();
+        }
+    }
+
+    if let Some(target) = hud.challenge_score {
+        context.set_text_align("right");
+        context.fill_text(&fill(strings.beat, &[&target]), width - 8.0, 20.0).unwrap();
+    }
+
+    let Some((title, line)) = result_banner(hud, strings) else {
+        return;
+    };
+
+    context.set_text_align("center");
+    context.set_font("24px monospace");
+    context.fill_text(&title, width / 2.0, height / 2.0 - 16.0).unwrap();
+    context.set_font("16px monospace");
+    context.fill_text(&line, width / 2.0, height / 2.0 + 12.0).unwrap();
+}
+
+/// Title and second line announcing how the last run ended, if it has.
+pub(crate) fn result_banner(hud: &Hud, strings: &Strings) -> Option<(String, String)> {
+    let banner = match hud.result? {
+        RunResult::Score(score) => (
+            strings.game_over.to_string(),
+            match hud.challenge_score {
+                Some(target) if score > target => fill(strings.challenge_beaten, &[&score]),
+                _ => fill(strings.final_score, &[&score]),
+            },
+        ),
+        RunResult::Winner(Some(player)) => {
+            (fill(strings.player_wins, &[&(player + 1)]), strings.play_again.to_string())
+        }
+        RunResult::Winner(None) => (strings.draw.to_string(), strings.play_again.to_string()),
+    };
+    Some(banner)
+}
@@ -0,0 +1,181 @@
+//! Gamepads, polled every tick through `navigator.getGamepads()`: the first
+//! connected pad plays player 1 and the second player 2, steering with the
+//! D-pad or the left stick, with A (the bottom face button) to flap and to
+//! skip the attract demo. Pads can be plugged in and out at any time; one
+//! that goes away lets go of whatever it was holding. While a pad is
+//! connected the title screen shows a controller and how to start.
+
+use js_sys::Array;
+use wasm_bindgen::JsCast;
+use web_sys::{window, CanvasRenderingContext2d, Gamepad, GamepadButton, HtmlCanvasElement};
+
+use crate::input::Action;
+use crate::theme::Style;
+
+const PADS: usize = 2;
+/// How far the stick has to lean before it counts as a direction.
+const STICK_DEADZONE: f64 = 0.5;
+/// Buttons in the browser's standard gamepad mapping.
+const BUTTON_A: u32 = 0;
+const DPAD_BUTTONS: [(u32, Action); 4] = [
+    (12, Action::Up),
+    (13, Action::Down),
+    (14, Action::Left),
+    (15, Action::Right),
+];
+
+/// What one pad is holding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct PadState {
+    direction: Option<Action>,
+    a: bool,
+}
+
+pub(crate) struct Gamepads {
+    /// By player, as of the last poll; default for a pad that isn't there.
+    states: [PadState; PADS],
+    connected: bool,
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+}
+
+impl Gamepads {
+    pub(crate) fn new(canvas: &HtmlCanvasElement) -> Gamepads {
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+        Gamepads { states: Default::default(), connected: false, canvas: canvas.clone(), context }
+    }
+
+    /// Reads every pad and returns what changed since the last poll, as
+    /// `(player, action, pressed)` in the order to apply them.
+    pub(crate) fn poll(&mut self) -> Vec<(usize, Action, bool)> {
+        let pads: Vec<PadState> = connected_pads().iter().map(read).collect();
+        self.connected = !pads.is_empty();
+        let (states, changes) = assign(&self.states, &pads);
+        self.states = states;
+        changes
+    }
+
+    /// A controller and how to start, over the title screen, while a pad is
+    /// connected; `flaps` for Flappy Bird's A rather than the grid games'
+    /// D-pad.
+    pub(crate) fn render_prompt(&self, style: &Style, flaps: bool) {
+        if !self.connected {
+            return;
+        }
+        let (palette, strings) = (style.palette(), style.strings());
+        let context = &self.context;
+        let (cx, cy) = (self.canvas.width() as f64 / 2.0, self.canvas.height() as f64 * 0.25);
+
+        // ✅ A pill-shaped pad with a D-pad cross on the left and A on the right
+        context.set_fill_style_str(palette.text);
+        context.fill_rect(cx - 18.0, cy - 12.0, 36.0, 24.0);
+        for x in [cx - 18.0, cx + 18.0] {
+            context.begin_path();
+            context.arc(x, cy, 12.0, 0.0, std::f64::consts::TAU).unwrap();
+            context.fill();
+        }
+        context.set_fill_style_str(palette.background);
+        context.fill_rect(cx - 24.0, cy - 2.0, 12.0, 4.0);
+        context.fill_rect(cx - 20.0, cy - 6.0, 4.0, 12.0);
+        context.set_fill_style_str(if flaps { palette.highlight } else { palette.background });
+        context.begin_path();
+        context.arc(cx + 18.0, cy, 4.0, 0.0, std::f64::consts::TAU).unwrap();
+        context.fill();
+
+        context.set_fill_style_str(palette.text);
+        context.set_font("16px monospace");
+        context.set_text_align("center");
+        let prompt = if flaps { strings.pad_flap } else { strings.pad_steer };
+        context.fill_text(prompt, cx, cy + 34.0).unwrap();
+    }
+}
+
+/// Hands what the connected `pads` hold, in order, to the players, and
+/// returns what changed since `old`.
+fn assign(old: &[PadState; PADS], pads: &[PadState]) -> ([PadState; PADS], Vec<(usize, Action, bool)>) {
+    // ✅ Players by position among the connected pads: the browser's index
+    // keeps a gap where a pad was unplugged, and can go past 1
+    let mut states = [PadState::default(); PADS];
+    for (state, pad) in states.iter_mut().zip(pads) {
+        *state = *pad;
+    }
+
+    let mut changes = Vec::new();
+    for (player, (old, new)) in old.iter().zip(&states).enumerate() {
+        if old.direction != new.direction {
+            changes.extend(old.direction.map(|action| (player, action, false)));
+            changes.extend(new.direction.map(|action| (player, action, true)));
+        }
+        if new.a && !old.a {
+            changes.push((player, Action::Flap, true));
+        }
+    }
+    (states, changes)
+}
+
+fn connected_pads() -> Vec<Gamepad> {
+    let Some(pads) = window().and_then(|window| window.navigator().get_gamepads().ok()) else {
+        return Vec::new();
+    };
+    pads.iter()
+        .filter_map(|pad| pad.dyn_into::<Gamepad>().ok())
+        .filter(Gamepad::connected)
+        .collect()
+}
+
+fn read(pad: &Gamepad) -> PadState {
+    let buttons = pad.buttons();
+    let pressed = |index: u32| {
+        buttons
+            .get(index)
+            .dyn_into::<GamepadButton>()
+            .is_ok_and(|button| button.pressed())
+    };
+
+    let dpad = DPAD_BUTTONS.iter().find(|&&(index, _)| pressed(index)).map(|&(_, action)| action);
+    PadState { direction: dpad.or_else(|| stick(&pad.axes())), a: pressed(BUTTON_A) }
+}
+
+/// The left stick's direction, along whichever axis it leans further.
+fn stick(axes: &Array) -> Option<Action> {
+    let (x, y) = (axes.get(0).as_f64()?, axes.get(1).as_f64()?);
+    if x.abs().max(y.abs()) < STICK_DEADZONE {
+        return None;
+    }
+    Some(match (x.abs() > y.abs(), x > 0.0, y > 0.0) {
+        (true, true, _) => Action::Right,
+        (true, false, _) => Action::Left,
+        (false, _, true) => Action::Down,
+        (false, _, false) => Action::Up,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: PadState = PadState { direction: Some(Action::Left), a: false };
+    const A: PadState = PadState { direction: None, a: true };
+
+    #[test]
+    fn players_follow_the_connected_pads_in_order() {
+        let (states, changes) = assign(&Default::default(), &[LEFT, A]);
+        assert_eq!(changes, [(0, Action::Left, true), (1, Action::Flap, true)]);
+
+        // ✅ The first pad unplugged: the second moves up to player 1, and
+        // player 2 lets go of everything
+        let (states, changes) = assign(&states, &[A]);
+        assert_eq!(changes, [(0, Action::Left, false), (0, Action::Flap, true)]);
+        assert_eq!(states, [A, PadState::default()]);
+
+        // ✅ Pads past the second have no player
+        let (states, changes) = assign(&states, &[A, LEFT, LEFT]);
+        assert_eq!(changes, [(1, Action::Left, true)]);
+        assert_eq!(states, [A, LEFT]);
+    }
+}
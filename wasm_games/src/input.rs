@@ -0,0 +1,79 @@
+//! Keyboard input for up to two players sharing one keyboard: player 1 on the
+//! arrow keys (Space also flaps), player 2 on WASD.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Flap,
+}
+
+impl Action {
+    /// Grid step for the movement actions.
+    pub fn direction(self) -> Option<(i32, i32)> {
+        match self {
+            Action::Up => Some((0, -1)),
+            Action::Down => Some((0, 1)),
+            Action::Left => Some((-1, 0)),
+            Action::Right => Some((1, 0)),
+            Action::Flap => None,
+        }
+    }
+}
+
+/// Which player a key belongs to and what it does. Player 2 is matched on
+/// `code` so WASD stays in the same place on non-QWERTY layouts.
+pub fn key_binding(key: &str, code: &str) -> Option<(usize, Action)> {
+    let binding = match code {
+        "KeyW" => (1, Action::Up),
+        "KeyS" => (1, Action::Down),
+        "KeyA" => (1, Action::Left),
+        "KeyD" => (1, Action::Right),
+        "Space" => (0, Action::Flap),
+        _ => match key {
+            "ArrowUp" => (0, Action::Up),
+            "ArrowDown" => (0, Action::Down),
+            "ArrowLeft" => (0, Action::Left),
+            "ArrowRight" => (0, Action::Right),
+            _ => return None,
+        },
+    };
+    Some(binding)
+}
+
+/// Direction keys one player is holding down (Pac-Man only moves while one is pressed).
+#[derive(Default)]
+pub struct HeldDirections {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+}
+
+impl HeldDirections {
+    pub fn set(&mut self, action: Action, pressed: bool) {
+        match action {
+            Action::Up => self.up = pressed,
+            Action::Down => self.down = pressed,
+            Action::Left => self.left = pressed,
+            Action::Right => self.right = pressed,
+            Action::Flap => {}
+        }
+    }
+
+    pub fn direction(&self) -> (i32, i32) {
+        if self.up {
+            (0, -1)
+        } else if self.down {
+            (0, 1)
+        } else if self.left {
+            (-1, 0)
+        } else if self.right {
+            (1, 0)
+        } else {
+            (0, 0)
+        }
+    }
+}
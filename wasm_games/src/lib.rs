@@ -3,12 +3,60 @@ use wasm_bindgen::prelude::*;
 mod flappy;
 mod snake;
 mod pacman;
+mod game;
+mod element;
+mod config;
+mod rng;
+mod challenge;
+mod events;
+mod achievements;
+mod input;
+mod lockstep;
+mod net;
+mod online;
+mod view;
+mod draw;
+mod spectate;
+mod broadcast;
+mod sim;
+mod replay;
+mod agent;
+mod attract;
+mod touch;
+mod gamepad;
+mod path;
+mod env;
+mod theme;
+mod locale;
+mod utils;
+mod announce;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod term;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod raster;
 
-pub use flappy::FlappyBird;
-pub use snake::SnakeGame;
-pub use pacman::PacmanGame;
+pub use flappy::{FlappyBird, FlappySim};
+pub use snake::{SnakeGame, SnakeSim};
+pub use pacman::{PacmanGame, PacmanSim};
+pub use config::{ConfigError, GameConfig, TouchConfig, Tuning};
+pub use game::{Game, GameKind};
+pub use achievements::achievements;
+pub use lockstep::{Lockstep, HASH_INTERVAL, TICK};
+pub use spectate::{Publisher, SpectateMessage, Viewer};
+pub use view::View;
+pub use draw::{Canvas, DisplayList, DrawCommand};
+pub use events::{DeathCause, GameEvent};
+pub use sim::{new_sim, Command, Sim};
+pub use agent::{agent, Agent, AGENTS};
+pub use attract::Attract;
+pub use env::{Env, Observation, ObservationKind, Step};
+pub use theme::{Palette, Style, Theme};
+pub use locale::{fill, Language, Strings};
+pub use replay::{Recorder, Replay, ReplayError, Verified, END_GRACE, MAX_TICKS};
+pub use net::{ClientMessage, ServerMessage, DEFAULT_INPUT_DELAY, ROOM_SIZE};
 
 #[wasm_bindgen(start)]
 pub fn start() {
-    console_error_panic_hook::set_once();
+    utils::set_panic_hook();
+    element::register();
 }
@@ -0,0 +1,363 @@
+//! User-facing text in English, Spanish and Portuguese. Like the theme
+//! palettes, each locale is a `Strings` constant, so a key missing from any
+//! of them doesn't compile. Text drawn on the canvas, read out by the
+//! announcer (`announce.rs`) and shown on the page around it (`Game::strings`)
+//! all comes from here.
+//!
+//! `?lang=` (or a `lang` attribute, or `Game::set_language`) takes a list of
+//! language tags in order of preference, like `navigator.languages`: each
+//! falls back from region to language (`pt-BR` to `pt`), and English is the
+//! last resort.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::achievements::ACHIEVEMENTS;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Es,
+    Pt,
+}
+
+/// Text templates: `{}` marks where `fill` puts a value, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Strings {
+    pub flappy_title: &'static str,
+    pub snake_title: &'static str,
+    pub pacman_title: &'static str,
+
+    // ✅ HUD and end-of-run banner
+    pub score: &'static str,
+    pub player_score: &'static str,
+    pub beat: &'static str,
+    pub game_over: &'static str,
+    pub final_score: &'static str,
+    pub challenge_beaten: &'static str,
+    pub player_wins: &'static str,
+    pub draw: &'static str,
+    pub play_again: &'static str,
+    pub press_start: &'static str,
+    /// Title screen prompts while a gamepad is connected.
+    pub pad_flap: &'static str,
+    pub pad_steer: &'static str,
+
+    // ✅ Online and spectator status lines
+    pub connecting: &'static str,
+    pub waiting_for_opponent: &'static str,
+    pub opponent_left: &'static str,
+    pub out_of_sync: &'static str,
+    pub room_full: &'static str,
+    pub disconnected: &'static str,
+    pub you_are: &'static str,
+    pub waiting_for_channel: &'static str,
+
+    pub achievement_unlocked: &'static str,
+    /// Title and description of each achievement, in `ACHIEVEMENTS` order.
+    pub achievements: [(&'static str, &'static str); ACHIEVEMENTS.len()],
+
+    // ✅ Screen reader announcements
+    pub started: &'static str,
+    pub maze_cleared: &'static str,
+    pub ghost_eaten: &'static str,
+    pub player_out: &'static str,
+    pub points: &'static str,
+    pub player_points: &'static str,
+    pub over_score: &'static str,
+    pub over_challenge_beaten: &'static str,
+    pub over_player_wins: &'static str,
+    pub over_draw: &'static str,
+    pub cause_pipe: &'static str,
+    pub cause_ground: &'static str,
+    pub cause_ceiling: &'static str,
+    pub cause_wall: &'static str,
+    pub cause_own_body: &'static str,
+    pub cause_other_snake: &'static str,
+    pub cause_head_on: &'static str,
+    pub cause_ghost: &'static str,
+
+    // ✅ The page around the canvas (`index.html`)
+    pub theme_label: &'static str,
+    pub cues_label: &'static str,
+    pub speed_label: &'static str,
+    pub motion_label: &'static str,
+    pub dpad_label: &'static str,
+    pub language_label: &'static str,
+    pub share_challenge: &'static str,
+    pub link_copied: &'static str,
+    pub challenge_link: &'static str,
+    pub submit_score: &'static str,
+    pub leaderboard_name: &'static str,
+    pub ranked: &'static str,
+    pub leaderboard_unreachable: &'static str,
+}
+
+const EN: Strings = Strings {
+    flappy_title: "Flappy Bird",
+    snake_title: "Snake",
+    pacman_title: "Pac-Man",
+
+    score: "Score: {}",
+    player_score: "P{}: {}",
+    beat: "Beat: {}",
+    game_over: "GAME OVER",
+    final_score: "Score {}",
+    challenge_beaten: "Score {} - challenge beaten!",
+    player_wins: "PLAYER {} WINS",
+    draw: "DRAW",
+    play_again: "Press a key to play again",
+    press_start: "PRESS START",
+    pad_flap: "Press A to flap",
+    pad_steer: "Press the D-pad to start",
+
+    connecting: "Connecting...",
+    waiting_for_opponent: "Waiting for an opponent...",
+    opponent_left: "Opponent left - waiting for a new one",
+    out_of_sync: "Out of sync at tick {}",
+    room_full: "Room is full",
+    disconnected: "Disconnected from the relay",
+    you_are: "You are P{}",
+    waiting_for_channel: "Waiting for \"{}\"...",
+
+    achievement_unlocked: "Achievement unlocked: {}",
+    achievements: [
+        ("Lift Off", "Pass your first pipe"),
+        ("Frequent Flyer", "Pass 50 pipes in one run"),
+        ("Growth Spurt", "Grow the snake to length 25"),
+        ("Centipede", "Grow the snake to length 100"),
+        ("Clean Plate", "Clear a maze"),
+        ("Non-Stop", "Clear a maze without ever stopping"),
+        ("Ghostbuster", "Eat four ghosts on one power pellet"),
+    ],
+
+    started: "{} started.",
+    maze_cleared: "Maze cleared.",
+    ghost_eaten: "Ghost eaten.",
+    player_out: "Player {} is out: {}.",
+    points: "{} points.",
+    player_points: "Player {}: {} points.",
+    over_score: "Game over. Final score {}.",
+    over_challenge_beaten: "Game over. Final score {}, challenge beaten!",
+    over_player_wins: "Game over. Player {} wins.",
+    over_draw: "Game over. It's a draw.",
+    cause_pipe: "hit a pipe",
+    cause_ground: "hit the ground",
+    cause_ceiling: "flew off the top",
+    cause_wall: "hit a wall",
+    cause_own_body: "ran into itself",
+    cause_other_snake: "ran into the other snake",
+    cause_head_on: "crashed head-on",
+    cause_ghost: "caught by a ghost",
+
+    theme_label: "Theme",
+    cues_label: "Shape cues",
+    speed_label: "Speed",
+    motion_label: "Reduced motion",
+    dpad_label: "On-screen D-pad",
+    language_label: "Language",
+    share_challenge: "Share challenge",
+    link_copied: "Link copied!",
+    challenge_link: "Challenge link",
+    submit_score: "Submit score",
+    leaderboard_name: "Name for the leaderboard",
+    ranked: "Ranked #{}!",
+    leaderboard_unreachable: "Leaderboard unreachable",
+};
+
+const ES: Strings = Strings {
+    flappy_title: "Flappy Bird",
+    snake_title: "Snake",
+    pacman_title: "Pac-Man",
+
+    score: "Puntos: {}",
+    player_score: "J{}: {}",
+    beat: "Meta: {}",
+    game_over: "FIN DEL JUEGO",
+    final_score: "Puntuación {}",
+    challenge_beaten: "Puntuación {}: ¡reto superado!",
+    player_wins: "GANA EL JUGADOR {}",
+    draw: "EMPATE",
+    play_again: "Pulsa una tecla para volver a jugar",
+    press_start: "PULSA INICIO",
+    pad_flap: "Pulsa A para aletear",
+    pad_steer: "Pulsa la cruceta para empezar",
+
+    connecting: "Conectando...",
+    waiting_for_opponent: "Esperando a un rival...",
+    opponent_left: "El rival se fue: esperando a otro",
+    out_of_sync: "Desincronizado en el tick {}",
+    room_full: "La sala está llena",
+    disconnected: "Desconectado del relé",
+    you_are: "Eres J{}",
+    waiting_for_channel: "Esperando a \"{}\"...",
+
+    achievement_unlocked: "Logro desbloqueado: {}",
+    achievements: [
+        ("Despegue", "Pasa tu primer tubo"),
+        ("Viajero frecuente", "Pasa 50 tubos en una partida"),
+        ("Estirón", "Haz crecer la serpiente hasta 25"),
+        ("Ciempiés", "Haz crecer la serpiente hasta 100"),
+        ("Plato limpio", "Despeja un laberinto"),
+        ("Sin parar", "Despeja un laberinto sin detenerte nunca"),
+        ("Cazafantasmas", "Come cuatro fantasmas con una sola píldora de poder"),
+    ],
+
+    started: "{}: empieza la partida.",
+    maze_cleared: "Laberinto despejado.",
+    ghost_eaten: "Fantasma comido.",
+    player_out: "Jugador {} eliminado: {}.",
+    points: "{} puntos.",
+    player_points: "Jugador {}: {} puntos.",
+    over_score: "Fin del juego. Puntuación final {}.",
+    over_challenge_beaten: "Fin del juego. Puntuación final {}, ¡reto superado!",
+    over_player_wins: "Fin del juego. Gana el jugador {}.",
+    over_draw: "Fin del juego. Empate.",
+    cause_pipe: "chocó con un tubo",
+    cause_ground: "chocó contra el suelo",
+    cause_ceiling: "se salió por arriba",
+    cause_wall: "chocó con una pared",
+    cause_own_body: "chocó consigo misma",
+    cause_other_snake: "chocó con la otra serpiente",
+    cause_head_on: "chocó de frente",
+    cause_ghost: "atrapado por un fantasma",
+
+    theme_label: "Tema",
+    cues_label: "Pistas de forma",
+    speed_label: "Velocidad",
+    motion_label: "Movimiento reducido",
+    dpad_label: "Cruceta en pantalla",
+    language_label: "Idioma",
+    share_challenge: "Compartir reto",
+    link_copied: "¡Enlace copiado!",
+    challenge_link: "Enlace del reto",
+    submit_score: "Enviar puntuación",
+    leaderboard_name: "Nombre para la clasificación",
+    ranked: "¡Puesto {}!",
+    leaderboard_unreachable: "Clasificación no disponible",
+};
+
+const PT: Strings = Strings {
+    flappy_title: "Flappy Bird",
+    snake_title: "Snake",
+    pacman_title: "Pac-Man",
+
+    score: "Pontos: {}",
+    player_score: "J{}: {}",
+    beat: "Meta: {}",
+    game_over: "FIM DE JOGO",
+    final_score: "Pontuação {}",
+    challenge_beaten: "Pontuação {} - desafio vencido!",
+    player_wins: "JOGADOR {} VENCEU",
+    draw: "EMPATE",
+    play_again: "Aperte uma tecla para jogar de novo",
+    press_start: "APERTE INICIAR",
+    pad_flap: "Aperte A para bater as asas",
+    pad_steer: "Aperte o direcional para começar",
+
+    connecting: "Conectando...",
+    waiting_for_opponent: "Esperando um adversário...",
+    opponent_left: "O adversário saiu - esperando outro",
+    out_of_sync: "Dessincronizado no tick {}",
+    room_full: "A sala está cheia",
+    disconnected: "Desconectado do relay",
+    you_are: "Você é J{}",
+    waiting_for_channel: "Esperando \"{}\"...",
+
+    achievement_unlocked: "Conquista desbloqueada: {}",
+    achievements: [
+        ("Decolagem", "Passe pelo primeiro cano"),
+        ("Passageiro frequente", "Passe por 50 canos em uma partida"),
+        ("Estirão", "Faça a cobra chegar a 25"),
+        ("Centopeia", "Faça a cobra chegar a 100"),
+        ("Prato limpo", "Limpe um labirinto"),
+        ("Sem parar", "Limpe um labirinto sem nunca parar"),
+        ("Caça-fantasmas", "Coma quatro fantasmas com uma só pílula de poder"),
+    ],
+
+    started: "{}: a partida começou.",
+    maze_cleared: "Labirinto limpo.",
+    ghost_eaten: "Fantasma comido.",
+    player_out: "Jogador {} eliminado: {}.",
+    points: "{} pontos.",
+    player_points: "Jogador {}: {} pontos.",
+    over_score: "Fim de jogo. Pontuação final {}.",
+    over_challenge_beaten: "Fim de jogo. Pontuação final {}, desafio vencido!",
+    over_player_wins: "Fim de jogo. O jogador {} venceu.",
+    over_draw: "Fim de jogo. Empate.",
+    cause_pipe: "bateu num cano",
+    cause_ground: "bateu no chão",
+    cause_ceiling: "saiu por cima",
+    cause_wall: "bateu numa parede",
+    cause_own_body: "bateu em si mesma",
+    cause_other_snake: "bateu na outra cobra",
+    cause_head_on: "bateu de frente",
+    cause_ghost: "pego por um fantasma",
+
+    theme_label: "Tema",
+    cues_label: "Dicas de forma",
+    speed_label: "Velocidade",
+    motion_label: "Movimento reduzido",
+    dpad_label: "Direcional na tela",
+    language_label: "Idioma",
+    share_challenge: "Compartilhar desafio",
+    link_copied: "Link copiado!",
+    challenge_link: "Link do desafio",
+    submit_score: "Enviar pontuação",
+    leaderboard_name: "Nome para o placar",
+    ranked: "{}º lugar!",
+    leaderboard_unreachable: "Placar indisponível",
+};
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::En, Language::Es, Language::Pt];
+
+    /// The language's code, as `?lang=` takes it.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Es => "es",
+            Language::Pt => "pt",
+        }
+    }
+
+    /// One language tag such as `pt-BR`, matched by its primary language.
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let tag = tag.trim().to_ascii_lowercase();
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        match primary {
+            "en" => Some(Language::En),
+            "es" => Some(Language::Es),
+            "pt" => Some(Language::Pt),
+            _ => None,
+        }
+    }
+
+    /// The first bundled language in a comma-separated preference list,
+    /// else English.
+    pub fn negotiate(tags: &str) -> Language {
+        tags.split(',').find_map(Language::from_tag).unwrap_or_default()
+    }
+
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Language::En => &EN,
+            Language::Es => &ES,
+            Language::Pt => &PT,
+        }
+    }
+}
+
+/// `template` with each `{}` replaced by the next of `values`.
+pub fn fill(template: &str, values: &[&dyn fmt::Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (value, part) in values.iter().zip(parts) {
+        text.push_str(&value.to_string());
+        text.push_str(part);
+    }
+    text
+}
@@ -0,0 +1,121 @@
+//! Deterministic lockstep: every peer runs the same headless simulation from
+//! the same seed and only advances a tick once it has every player's input
+//! for it. Local input is scheduled `delay` ticks ahead, which hides the round
+//! trip through the relay as long as it's shorter than the delay.
+
+use std::collections::BTreeMap;
+
+/// Length of one lockstep tick in seconds; the same fixed step the hosts use.
+pub const TICK: f64 = 1.0 / 60.0;
+/// Peers send a state hash every this many ticks.
+pub const HASH_INTERVAL: u32 = 30;
+
+/// One player's input for one tick: a new direction, or nothing.
+pub type Input = Option<(i32, i32)>;
+
+pub struct Lockstep {
+    players: usize,
+    local: usize,
+    delay: u32,
+    tick: u32,
+    next_local_tick: u32,
+    /// Inputs received so far for ticks that haven't run yet.
+    inputs: BTreeMap<u32, Vec<Option<Input>>>,
+}
+
+impl Lockstep {
+    /// Starts at tick 0 with nobody pressing anything for the first `delay` ticks.
+    pub fn new(players: usize, local: usize, delay: u32) -> Lockstep {
+        let inputs = (0..delay).map(|tick| (tick, vec![Some(None); players])).collect();
+        Lockstep {
+            players,
+            local,
+            delay,
+            tick: 0,
+            next_local_tick: delay,
+            inputs,
+        }
+    }
+
+    /// The next tick to run.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Whether the local player still has to send an input `delay` ticks ahead.
+    pub fn can_schedule(&self) -> bool {
+        self.next_local_tick <= self.tick + self.delay
+    }
+
+    /// Records the local player's input for the next free tick and returns
+    /// that tick, to be sent to the other peers.
+    pub fn schedule(&mut self, input: Input) -> u32 {
+        let tick = self.next_local_tick;
+        self.next_local_tick += 1;
+        self.receive(self.local, tick, input);
+        tick
+    }
+
+    /// Records an input from any player. Inputs for ticks that already ran,
+    /// and repeats of one already recorded, are ignored.
+    pub fn receive(&mut self, player: usize, tick: u32, input: Input) {
+        if tick < self.tick || player >= self.players {
+            return;
+        }
+        let players = self.players;
+        let slot = &mut self.inputs.entry(tick).or_insert_with(|| vec![None; players])[player];
+        slot.get_or_insert(input);
+    }
+
+    /// Every player's input for the next tick, moving on to the one after;
+    /// `None` while some peer's input hasn't arrived yet.
+    pub fn advance(&mut self) -> Option<Vec<Input>> {
+        let inputs = self.inputs.get(&self.tick)?;
+        if inputs.iter().any(Option::is_none) {
+            return None;
+        }
+
+        let inputs = self.inputs.remove(&self.tick)?;
+        self.tick += 1;
+        Some(inputs.into_iter().flatten().collect())
+    }
+}
+
+/// FNV-1a over explicitly sized values, so native and wasm32 peers agree
+/// (`std`'s hashers are neither guaranteed stable nor width-independent).
+pub struct StateHasher(u64);
+
+impl StateHasher {
+    pub fn new() -> StateHasher {
+        StateHasher(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for StateHasher {
+    fn default() -> StateHasher {
+        StateHasher::new()
+    }
+}
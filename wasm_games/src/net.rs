@@ -0,0 +1,47 @@
+//! Messages between browsers and the relay (`src/bin/relay.rs`), sent as JSON
+//! text frames over a WebSocket. The relay never runs the game: it pairs
+//! players up in rooms, hands out a seed, forwards inputs and compares the
+//! state hashes the peers report. It also carries spectator channels from one
+//! publisher to its watchers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::lockstep::Input;
+use crate::spectate::SpectateMessage;
+
+/// Players in an online room.
+pub const ROOM_SIZE: usize = 2;
+/// Ticks between pressing a key and it taking effect, unless the relay says otherwise.
+pub const DEFAULT_INPUT_DELAY: u32 = 6;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join { room: String },
+    /// The sender's input for `tick`; one is sent for every tick, even when idle.
+    Input { match_id: u32, tick: u32, direction: Input },
+    /// The sender's state hash after running `tick`.
+    Hash { match_id: u32, tick: u32, hash: u64 },
+    /// Become the publisher on a spectator channel, replacing any earlier one.
+    Publish { channel: String },
+    Watch { channel: String },
+    /// From the publisher to every watcher, or from a watcher to the publisher.
+    Spectate { message: SpectateMessage },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Joined as `player`; the room is waiting for an opponent.
+    Waiting { player: usize },
+    /// The room is full: everybody starts a new match from `seed`. Messages
+    /// still in flight from an earlier match are dropped by its `match_id`.
+    Start { player: usize, match_id: u32, seed: u64, input_delay: u32 },
+    Input { player: usize, tick: u32, direction: Input },
+    /// `player` disconnected; the match is over.
+    PeerLeft { player: usize },
+    /// The peers' hashes for `tick` didn't match.
+    Desync { tick: u32 },
+    RoomFull,
+    Spectate { message: SpectateMessage },
+}
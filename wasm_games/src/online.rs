@@ -0,0 +1,243 @@
+//! Online versus snake (`?game=snake&online=ws://localhost:9001&room=lobby`).
+//!
+//! Both browsers run the same `SnakeSim` in lockstep (see `lockstep.rs`); the
+//! relay only forwards inputs. Every `HASH_INTERVAL` ticks each side reports
+//! its state hash and the relay flags the match if they ever differ.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, Event, HtmlCanvasElement, MessageEvent, WebSocket};
+
+use crate::config::GameConfig;
+use crate::lockstep::{self, Input, Lockstep};
+use crate::net::{self, ClientMessage, ServerMessage};
+use crate::locale::fill;
+use crate::theme::Style;
+use crate::view::View;
+use crate::SnakeGame;
+
+type MessageListener = Closure<dyn FnMut(MessageEvent)>;
+type EventListener = Closure<dyn FnMut(Event)>;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Connecting,
+    Waiting,
+    Playing,
+    OpponentLeft,
+    Desync(u32),
+    RoomFull,
+    Disconnected,
+}
+
+/// A match in progress: the local game and where it is in the lockstep.
+struct Session {
+    match_id: u32,
+    game: SnakeGame,
+    lockstep: Lockstep,
+}
+
+pub struct OnlineSnake {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    config: GameConfig,
+    socket: WebSocket,
+    inbox: Rc<RefCell<VecDeque<ServerMessage>>>,
+    closed: Rc<Cell<bool>>,
+    _listeners: (EventListener, MessageListener, EventListener),
+    status: Status,
+    player: Option<usize>,
+    session: Option<Session>,
+    direction: Input, // ✅ Latest key press, sent with the next scheduled tick
+}
+
+impl OnlineSnake {
+    pub fn new(config: &GameConfig, canvas: HtmlCanvasElement) -> Result<OnlineSnake, JsValue> {
+        let online = config.online().expect("online config");
+        let context = canvas
+            .get_context("2d")?
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()?;
+        let socket = WebSocket::new(&online.server)?;
+
+        let join_socket = socket.clone();
+        let join = ClientMessage::Join { room: online.room.clone() };
+        let on_open = Closure::<dyn FnMut(Event)>::new(move |_event: Event| {
+            send(&join_socket, &join);
+        });
+
+        let inbox = Rc::new(RefCell::new(VecDeque::new()));
+        let message_inbox = inbox.clone();
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(text) = event.data().as_string() else { return };
+            match serde_json::from_str(&text) {
+                Ok(message) => message_inbox.borrow_mut().push_back(message),
+                Err(err) => web_sys::console::warn_1(&format!("online: bad message: {}", err).into()),
+            }
+        });
+
+        let closed = Rc::new(Cell::new(false));
+        let close_flag = closed.clone();
+        let on_close = Closure::<dyn FnMut(Event)>::new(move |_event: Event| close_flag.set(true));
+
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        Ok(OnlineSnake {
+            canvas,
+            context,
+            config: config.clone(),
+            socket,
+            inbox,
+            closed,
+            _listeners: (on_open, on_message, on_close),
+            status: Status::Connecting,
+            player: None,
+            session: None,
+            direction: None,
+        })
+    }
+
+    /// Runs at most one lockstep tick; stalls while the opponent's input for it is missing.
+    pub fn update(&mut self) {
+        self.handle_messages();
+
+        let Some(session) = &mut self.session else { return };
+        if self.status != Status::Playing {
+            return;
+        }
+
+        if session.lockstep.can_schedule() {
+            let direction = self.direction.take();
+            let tick = session.lockstep.schedule(direction);
+            send(&self.socket, &ClientMessage::Input { match_id: session.match_id, tick, direction });
+        }
+
+        let Some(inputs) = session.lockstep.advance() else { return };
+        for (player, input) in inputs.into_iter().enumerate() {
+            if let Some((dx, dy)) = input {
+                session.game.change_player_direction(player, dx, dy);
+            }
+        }
+        session.game.update(lockstep::TICK);
+
+        let tick = session.lockstep.tick() - 1;
+        if tick % lockstep::HASH_INTERVAL == 0 {
+            let hash = session.game.sim().state_hash();
+            send(&self.socket, &ClientMessage::Hash { match_id: session.match_id, tick, hash });
+        }
+    }
+
+    pub fn render(&self) {
+        let (palette, strings) = (self.config.style().palette(), self.config.style().strings());
+        if let Some(session) = &self.session {
+            session.game.render();
+        } else {
+            self.context.set_fill_style_str(palette.background);
+            self.context
+                .fill_rect(0.0, 0.0, self.canvas.width() as f64, self.canvas.height() as f64);
+        }
+
+        let message = match self.status {
+            Status::Connecting => strings.connecting.to_string(),
+            Status::Waiting => strings.waiting_for_opponent.to_string(),
+            Status::Playing => return,
+            Status::OpponentLeft => strings.opponent_left.to_string(),
+            Status::Desync(tick) => fill(strings.out_of_sync, &[&tick]),
+            Status::RoomFull => strings.room_full.to_string(),
+            Status::Disconnected => strings.disconnected.to_string(),
+        };
+
+        let (width, height) = (self.canvas.width() as f64, self.canvas.height() as f64);
+        self.context.set_fill_style_str(palette.text);
+        self.context.set_font("16px monospace");
+        self.context.set_text_align("center");
+        self.context.fill_text(&message, width / 2.0, height - 40.0).unwrap();
+        if let Some(player) = self.player {
+            self.context
+                .fill_text(&fill(strings.you_are, &[&(player + 1)]), width / 2.0, height - 16.0)
+                .unwrap();
+        }
+    }
+
+    /// Style for this match and the next.
+    pub fn set_style(&mut self, style: Style) {
+        self.config.style = style;
+        if let Some(session) = &mut self.session {
+            session.game.set_style(style);
+        }
+    }
+
+    /// The current match's frame, for spectators.
+    pub fn view(&self) -> Option<View> {
+        self.session.as_ref().map(|session| session.game.view())
+    }
+
+    /// Steers the local snake, whichever keys were used.
+    pub fn change_direction(&mut self, dx: i32, dy: i32) {
+        self.direction = Some((dx, dy));
+    }
+
+    fn handle_messages(&mut self) {
+        loop {
+            let Some(message) = self.inbox.borrow_mut().pop_front() else { break };
+            match message {
+                ServerMessage::Waiting { player } => {
+                    self.player = Some(player);
+                    self.status = Status::Waiting;
+                }
+                ServerMessage::Start { player, match_id, seed, input_delay } => {
+                    // ✅ Both sides build the same game from the relay's seed
+                    let mut config = self.config.clone();
+                    config.seed = Some(seed);
+                    config.players = net::ROOM_SIZE;
+                    self.player = Some(player);
+                    self.direction = None;
+                    self.session = Some(Session {
+                        match_id,
+                        game: SnakeGame::with_config(self.canvas.clone(), &config),
+                        lockstep: Lockstep::new(net::ROOM_SIZE, player, input_delay),
+                    });
+                    self.status = Status::Playing;
+                }
+                ServerMessage::Input { player, tick, direction } => {
+                    if let Some(session) = &mut self.session {
+                        session.lockstep.receive(player, tick, direction);
+                    }
+                }
+                ServerMessage::PeerLeft { .. } => self.status = Status::OpponentLeft,
+                ServerMessage::Desync { tick } => {
+                    web_sys::console::error_1(&format!("online: desync at tick {}", tick).into());
+                    self.status = Status::Desync(tick);
+                }
+                ServerMessage::RoomFull => self.status = Status::RoomFull,
+                ServerMessage::Spectate { .. } => {}
+            }
+        }
+
+        if self.closed.get() && self.status != Status::RoomFull {
+            self.status = Status::Disconnected;
+        }
+    }
+}
+
+impl Drop for OnlineSnake {
+    fn drop(&mut self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        self.socket.close().ok();
+    }
+}
+
+fn send(socket: &WebSocket, message: &ClientMessage) {
+    if socket.ready_state() != WebSocket::OPEN {
+        return;
+    }
+    let text = serde_json::to_string(message).expect("client messages serialize");
+    socket.send_with_str(&text).ok();
+}
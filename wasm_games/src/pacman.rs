@@ -1,22 +1,54 @@
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
-use js_sys::Math;
-use std::collections::VecDeque; // ✅ Used for BFS pathfinding
+use std::collections::VecDeque; // ✅ Frontier for maze generation
 use rand::Rng;
 use rand::seq::SliceRandom; // ✅ Enables `shuffle()` method
 
+use crate::achievements::Achievements;
+use crate::announce::Announcer;
+use crate::challenge;
+use crate::config::{GameConfig, MazeKind, PacmanConfig};
+use crate::draw::Canvas;
+use crate::env::{Observation, ObservationKind};
+use crate::events::{DeathCause, GameEvent};
+use crate::game::{self, GameKind, RunResult};
+use crate::lockstep::TICK;
+use crate::path::Search;
+use crate::replay::Recorder;
+use crate::rng::{self, GameRng};
+use crate::sim::{Command, Sim};
+use crate::theme::Style;
+use crate::view::{self, Hud, View};
+
+pub(crate) const GRID_SIZE: f64 = 20.0;
+pub(crate) const WIDTH: usize = 28;
+pub(crate) const HEIGHT: usize = 31;
+pub(crate) const PACMAN_MOVE_INTERVAL: f64 = 0.12; // ✅ Move every 120ms
+pub(crate) const GHOST_MOVE_INTERVAL: f64 = 0.25; // ✅ Move every 250ms
+pub(crate) const GHOST_RANDOMNESS: u32 = 30; // ✅ Percent of ghost moves that wander
+const POWER_PELLET_DURATION: f64 = 6.0; // ✅ Seconds ghosts stay frightened
+const GHOST_POINTS: u32 = 10;
+const PACMAN_START: (usize, usize) = (WIDTH / 2, HEIGHT / 2);
+const GHOST_STARTS: [(usize, usize); 4] = [(13, 11), (14, 11), (15, 11), (16, 11)];
+const GHOST_START_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+const GHOST_LETTERS: [&str; 4] = ["B", "P", "I", "C"]; // ✅ Shape cues: Blinky, Pinky, Inky, Clyde
+
+/// One frame of Pac-Man: the maze as text rows (`#` wall, `.` pellet,
+/// `o` power pellet), Pac-Man and the ghosts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PacmanView {
+    pub rows: Vec<String>,
+    pub pacman: (usize, usize),
+    pub ghosts: Vec<(usize, usize)>,
+    pub frightened: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controlled_ghost: Option<usize>,
+    pub hud: Hud,
+}
 
-const GRID_SIZE: f64 = 20.0;
-const WIDTH: usize = 28;
-const HEIGHT: usize = 31;
-const NUM_GHOSTS: usize = 4;
-const PACMAN_SPEED: u32 = 8;
-const GHOST_SPEED: u32 = 12;
-const PACMAN_MOVE_INTERVAL: f64 = 0.12; // ✅ Move every 120ms
-const GHOST_MOVE_INTERVAL: f64 = 0.25; // ✅ Move every 250ms
-
-#[wasm_bindgen]
-pub struct PacmanGame {
+/// The Pac-Man rules without a canvas. `PacmanGame` draws it and reacts to its events.
+pub struct PacmanSim {
     last_pacman_update: f64,
     last_ghost_update: f64,
     pacman: (usize, usize),
@@ -24,60 +56,127 @@ pub struct PacmanGame {
     next_direction: (i32, i32),
     ghosts: Vec<(usize, usize)>,
     ghost_directions: Vec<(i32, i32)>,
-    tick_counter: u32,
+    previous_pacman: (usize, usize), // ✅ Before the last move, for `motion`
+    previous_ghosts: Vec<(usize, usize)>,
     stopped: bool,
-    context: CanvasRenderingContext2d,
+    held: (i32, i32), // ✅ Direction the player is holding, `(0, 0)` for none
     grid: [[u8; WIDTH]; HEIGHT],
+    config: PacmanConfig,
+    game_config: GameConfig,
+    rng: GameRng,
+    seed: u64,
+    score: u32,
+    last_run: Option<(u64, u32)>, // ✅ (seed, score) of the last finished solo run
+    last_result: Option<RunResult>,
+    controlled_ghost: Option<usize>, // ✅ Ghost steered by player 2
+    ghost_input: (i32, i32),
+    waiting: bool, // ✅ No move since the last reset
+    has_stopped: bool, // ✅ Stood still at some point on this maze
+    pellets_left: u32,
+    frightened: f64, // ✅ Power pellet time left
+    ghost_streak: u32, // ✅ Ghosts eaten on the current power pellet
+    events: Vec<GameEvent>,
 }
 
-#[wasm_bindgen]
-impl PacmanGame {
-    #[wasm_bindgen(constructor)]
-    pub fn new(canvas: HtmlCanvasElement) -> PacmanGame {
-        let context = canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()
-            .unwrap();
-
-        let mut grid = [[0; WIDTH]; HEIGHT];
-
-        Self::generate_random_stage(&mut grid, 0.8);
-
-        let pacman_start = (WIDTH / 2, HEIGHT / 2);
-        let ghost_positions = vec![(13, 11), (14, 11), (15, 11), (16, 11)];
-        let ghost_directions = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
+impl PacmanSim {
+    pub fn new(game_config: &GameConfig) -> PacmanSim {
+        let config = game_config.pacman();
 
-        PacmanGame {
+        let mut sim = PacmanSim {
             last_pacman_update: 0.0,
             last_ghost_update: 0.0,
-            pacman: pacman_start,
+            pacman: PACMAN_START,
             direction: (0, 0),
             next_direction: (0, 0),
-            ghosts: ghost_positions,
-            ghost_directions,
+            ghosts: GHOST_STARTS.to_vec(),
+            ghost_directions: GHOST_START_DIRECTIONS.to_vec(),
+            previous_pacman: PACMAN_START,
+            previous_ghosts: GHOST_STARTS.to_vec(),
             stopped: true,
-            tick_counter: 0,
-            context,
-            grid,
-        }              
+            held: (0, 0),
+            grid: [[0; WIDTH]; HEIGHT],
+            config,
+            game_config: game_config.clone(),
+            rng: rng::seeded(0),
+            seed: 0,
+            score: 0,
+            last_run: None,
+            last_result: None,
+            controlled_ghost: (game_config.players() > 1).then_some(0),
+            ghost_input: (0, 0),
+            waiting: true,
+            has_stopped: false,
+            pellets_left: 0,
+            frightened: 0.0,
+            ghost_streak: 0,
+            events: Vec::new(),
+        };
+        sim.reset(); // ✅ Seeds the run and builds the maze from it
+        sim
+    }
+
+    fn generate_stage(grid: &mut [[u8; WIDTH]; HEIGHT], maze: MazeKind, rng: &mut GameRng) {
+        match maze {
+            MazeKind::Prim => Self::generate_random_stage(grid, 0.8, rng),
+            MazeKind::Corridors => Self::generate_random_stage(grid, 0.0, rng),
+            MazeKind::Open => Self::generate_open_stage(grid),
+        }
+        Self::place_power_pellets(grid);
+    }
+
+    /// ✅ Turns the pellet closest to each corner into a power pellet
+    fn place_power_pellets(grid: &mut [[u8; WIDTH]; HEIGHT]) {
+        let corners = [(1, 1), (WIDTH - 2, 1), (1, HEIGHT - 2), (WIDTH - 2, HEIGHT - 2)];
+
+        for &(cx, cy) in &corners {
+            let mut closest = None;
+            for (y, row) in grid.iter().enumerate() {
+                for (x, &cell) in row.iter().enumerate() {
+                    if cell == 2 {
+                        let distance = x.abs_diff(cx) + y.abs_diff(cy);
+                        if closest.is_none_or(|(best, _, _)| distance < best) {
+                            closest = Some((distance, x, y));
+                        }
+                    }
+                }
+            }
+
+            if let Some((_, x, y)) = closest {
+                grid[y][x] = 3; // Power pellet
+            }
+        }
+    }
+
+    /// Builds the next maze from the run's random stream.
+    fn new_stage(&mut self) {
+        Self::generate_stage(&mut self.grid, self.config.maze, &mut self.rng);
+        self.pellets_left = self.grid.iter().flatten().filter(|&&cell| cell == 2 || cell == 3).count() as u32;
+        self.has_stopped = false;
+        self.frightened = 0.0;
+        self.ghost_streak = 0;
+    }
+
+    /// ✅ Just the outer wall, pellets everywhere else
+    fn generate_open_stage(grid: &mut [[u8; WIDTH]; HEIGHT]) {
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let border = x == 0 || y == 0 || x == WIDTH - 1 || y == HEIGHT - 1;
+                *cell = if border { 1 } else { 2 };
+            }
+        }
     }
 
     /// ✅ Generates a maze-like grid with configurable openness
-    fn generate_random_stage(grid: &mut [[u8; WIDTH]; HEIGHT], density_factor: f64) {
-        let mut rng = rand::thread_rng();
+    fn generate_random_stage(grid: &mut [[u8; WIDTH]; HEIGHT], density_factor: f64, rng: &mut GameRng) {
 
         // 1. Fill the grid with walls
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                grid[y][x] = 1; // Wall
-            }
+        for row in grid.iter_mut() {
+            row.fill(1); // Wall
         }
 
         // 2. Choose a random start point
-        let start_x = rng.gen_range(1..WIDTH / 2) * 2; // Ensures odd indices
-        let start_y = rng.gen_range(1..HEIGHT / 2) * 2;
+        let start_x = rng.gen_range(1..(WIDTH / 2) as u32) as usize * 2; // Ensures odd indices
+        let start_y = rng.gen_range(1..(HEIGHT / 2) as u32) as usize * 2;
         grid[start_y][start_x] = 0; // Open path
 
         let mut frontier = VecDeque::new();
@@ -88,7 +187,7 @@ impl PacmanGame {
 
         while let Some((x, y)) = frontier.pop_front() {
             let mut possible_directions = directions.to_vec();
-            possible_directions.shuffle(&mut rng);
+            possible_directions.shuffle(rng);
         
             for &(dx, dy) in &possible_directions {
                 let nx = x as i32 + dx;
@@ -119,261 +218,622 @@ impl PacmanGame {
         // 4. Remove extra walls based on `density_factor`
         let remove_wall_count = ((WIDTH * HEIGHT) as f64 * density_factor) as usize;
         for _ in 0..remove_wall_count {
-            let x = rng.gen_range(1..WIDTH - 1);
-            let y = rng.gen_range(1..HEIGHT - 1);
+            let x = rng.gen_range(1..(WIDTH - 1) as u32) as usize;
+            let y = rng.gen_range(1..(HEIGHT - 1) as u32) as usize;
 
             if grid[y][x] == 1 {
                 grid[y][x] = 0; // Remove the wall to open the path
             }
         }
 
-        // 5. ✅ Ensure every open space has a pellet
-        for y in 1..HEIGHT - 1 {
-            for x in 1..WIDTH - 1 {
-                if grid[y][x] == 0 { 
-                    grid[y][x] = 2; // ✅ Every open space now has a pellet
+        // 5. ✅ Clear where Pac-Man and the ghosts start; each tile joins the maze
+        for (x, y) in GHOST_STARTS.into_iter().chain([PACMAN_START]) {
+            grid[y][x] = 0;
+        }
+
+        // 6. ✅ Ensure every open space has a pellet
+        for row in grid.iter_mut().take(HEIGHT - 1).skip(1) {
+            for cell in row.iter_mut().take(WIDTH - 1).skip(1) {
+                if *cell == 0 {
+                    *cell = 2; // ✅ Every open space now has a pellet
                 }
             }
         }
     }
 
-    #[wasm_bindgen]
     pub fn update(&mut self, delta_time: f64) {
+        self.steer(); // ✅ Keep going the held way, e.g. once a wall is out of the way
+        self.frightened = (self.frightened - delta_time).max(0.0);
+
         // ✅ Ghosts should always move independently
         self.last_ghost_update += delta_time;
-        if self.last_ghost_update >= GHOST_MOVE_INTERVAL {
+        if self.last_ghost_update >= self.config.ghost_move_interval {
             self.last_ghost_update = 0.0;
 
             let mut new_positions = Vec::new();
             let mut new_directions = Vec::new();
 
-            for (i, &ghost) in self.ghosts.iter().enumerate() {
-                let new_dir = self.get_next_ghost_move(ghost, self.pacman);
+            for (i, ghost) in self.ghosts.clone().into_iter().enumerate() {
+                let new_dir = if self.controlled_ghost == Some(i) {
+                    self.controlled_ghost_move(ghost)
+                } else if self.frightened > 0.0 {
+                    self.random_ghost_move(ghost).unwrap_or((0, 0)) // ✅ Frightened ghosts scatter
+                } else {
+                    self.get_next_ghost_move(ghost, self.pacman)
+                };
                 let new_pos = ((ghost.0 as i32 + new_dir.0) as usize, (ghost.1 as i32 + new_dir.1) as usize);
 
                 new_positions.push(new_pos);
                 new_directions.push(new_dir);
             }
 
-            self.ghosts = new_positions;
+            self.previous_ghosts = std::mem::replace(&mut self.ghosts, new_positions);
             self.ghost_directions = new_directions;
         }
 
         // ✅ Pac-Man only moves when an arrow key is actively pressed
         if self.stopped {
-            return; // ❌ Stop Pac-Man but keep ghosts moving
+            self.previous_pacman = self.pacman; // ✅ Standing still, not sliding
+            if !self.waiting {
+                self.has_stopped = true;
+            }
+        } else {
+            self.move_pacman(delta_time);
+            if self.pellets_left == 0 {
+                self.events.push(GameEvent::MazeCleared { stopped: self.has_stopped });
+                if self.controlled_ghost.is_some() {
+                    self.game_over(Some(0)); // ✅ Clearing the maze beats the ghost player
+                } else {
+                    self.next_maze();
+                }
+                return;
+            }
         }
 
+        // ✅ Check for collisions with ghosts, even while Pac-Man stands still
+        for i in 0..self.ghosts.len() {
+            if self.ghosts[i] != self.pacman {
+                continue;
+            }
+
+            if self.frightened > 0.0 {
+                self.ghosts[i] = GHOST_STARTS[i % GHOST_STARTS.len()];
+                self.ghost_streak += 1;
+                self.score += GHOST_POINTS;
+                self.events.push(GameEvent::GhostEaten { streak: self.ghost_streak });
+            } else {
+                self.events.push(GameEvent::Died { player: 0, cause: DeathCause::Ghost });
+                self.game_over(Some(1));
+                return;
+            }
+        }
+    }
+
+    fn move_pacman(&mut self, delta_time: f64) {
         self.last_pacman_update += delta_time;
-        if self.last_pacman_update >= PACMAN_MOVE_INTERVAL {
-            self.last_pacman_update = 0.0;
+        if self.last_pacman_update < self.config.pacman_move_interval {
+            return;
+        }
+        self.last_pacman_update = 0.0;
+        self.previous_pacman = self.pacman;
+
+        let (dx, dy) = self.direction;
+        let new_x = (self.pacman.0 as i32 + dx) as usize;
+        let new_y = (self.pacman.1 as i32 + dy) as usize;
 
-            let (dx, dy) = self.direction;
-            let new_x = (self.pacman.0 as i32 + dx) as usize;
-            let new_y = (self.pacman.1 as i32 + dy) as usize;
+        if self.grid[new_y][new_x] == 1 {
+            self.stopped = true; // ✅ Stop Pac-Man if he hits a wall
+            return;
+        }
 
-            if self.grid[new_y][new_x] != 1 {
-                self.pacman = (new_x, new_y);
+        self.pacman = (new_x, new_y);
+        match self.grid[new_y][new_x] {
+            2 => {
                 // Eat pellet
-                if self.grid[new_y][new_x] == 2 {
-                    self.grid[new_y][new_x] = 0;
-                }
-            } else {
-                self.stopped = true; // ✅ Stop Pac-Man if he hits a wall
+                self.grid[new_y][new_x] = 0;
+                self.score += 1;
+                self.pellets_left -= 1;
+            }
+            3 => {
+                // Eat power pellet: ghosts turn edible for a while
+                self.grid[new_y][new_x] = 0;
+                self.score += 1;
+                self.pellets_left -= 1;
+                self.frightened = POWER_PELLET_DURATION;
+                self.ghost_streak = 0;
             }
+            _ => {}
         }
+    }
 
-        // ✅ Check for collisions with ghosts
-        if self.ghosts.contains(&self.pacman) {
-            self.reset();
+    /// ✅ Same seed stream, fresh maze, score carries over
+    fn next_maze(&mut self) {
+        self.pacman = PACMAN_START;
+        self.direction = (0, 0);
+        self.next_direction = (0, 0);
+        self.stopped = true;
+        self.ghosts = GHOST_STARTS.to_vec();
+        self.ghost_directions = GHOST_START_DIRECTIONS.to_vec();
+        self.previous_pacman = PACMAN_START;
+        self.previous_ghosts = GHOST_STARTS.to_vec();
+        self.new_stage();
+    }
+
+    /// ✅ Player 2's held direction, unless it runs into a wall
+    fn controlled_ghost_move(&self, ghost: (usize, usize)) -> (i32, i32) {
+        let (dx, dy) = self.ghost_input;
+        let new_x = ghost.0 as i32 + dx;
+        let new_y = ghost.1 as i32 + dy;
+
+        if new_x >= 0 && new_x < WIDTH as i32 && new_y >= 0 && new_y < HEIGHT as i32
+            && self.grid[new_y as usize][new_x as usize] != 1
+        {
+            (dx, dy)
+        } else {
+            (0, 0)
         }
     }
 
+    /// ✅ A random direction that doesn't walk into a wall
+    fn random_ghost_move(&mut self, ghost: (usize, usize)) -> Option<(i32, i32)> {
+        let mut shuffled_directions = vec![(0, -1), (0, 1), (-1, 0), (1, 0)];
+        shuffled_directions.shuffle(&mut self.rng); // Randomize choices
+
+        for &(dx, dy) in &shuffled_directions {
+            let new_x = ghost.0 as i32 + dx;
+            let new_y = ghost.1 as i32 + dy;
+
+            if new_x >= 0 && new_x < WIDTH as i32 && new_y >= 0 && new_y < HEIGHT as i32 {
+                let new_x = new_x as usize;
+                let new_y = new_y as usize;
+
+                if self.grid[new_y][new_x] != 1 { // ✅ Ensure it's not a wall
+                    return Some((dx, dy));
+                }
+            }
+        }
+
+        None
+    }
+
     /// ✅ Improved Ghost Pathfinding (Prevents Moving into Walls)
-    fn get_next_ghost_move(&self, ghost: (usize, usize), target: (usize, usize)) -> (i32, i32) {
-        let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)]; // Up, Down, Left, Right
-        let mut queue = VecDeque::new();
-        let mut visited = [[false; WIDTH]; HEIGHT];
-        let mut parent = [[None; WIDTH]; HEIGHT];
-        let mut rng = rand::thread_rng();
-
-        // ✅ 30% chance to target a random tile instead of Pac-Man
-        let target = if rng.gen_range(0..100) < 30 {
-            (rng.gen_range(1..WIDTH - 1), rng.gen_range(1..HEIGHT - 1))
+    fn get_next_ghost_move(&mut self, ghost: (usize, usize), target: (usize, usize)) -> (i32, i32) {
+        let rng = &mut self.rng;
+
+        // ✅ `ghost_randomness`% chance to target a random tile instead of Pac-Man
+        let target = if rng.gen_range(0..100) < self.config.ghost_randomness {
+            (rng.gen_range(1..(WIDTH - 1) as u32) as usize, rng.gen_range(1..(HEIGHT - 1) as u32) as usize)
         } else {
             target
         };
 
-        queue.push_back(ghost);
-        visited[ghost.1][ghost.0] = true;
+        // ✅ Shortest paths never step onto a wall
+        let search = Search::new((WIDTH, HEIGHT), ghost, |(x, y)| self.grid[y][x] != 1);
 
-        while let Some((x, y)) = queue.pop_front() {
-            if (x, y) == target {
-                break; // Found the shortest path
+        // ✅ Same chance to make a wrong turn, but only to a valid tile
+        if rng.gen_range(0..100) < self.config.ghost_randomness {
+            if let Some(direction) = self.random_ghost_move(ghost) {
+                return direction;
             }
+        }
 
-            for &(dx, dy) in &directions {
-                let nx = x as i32 + dx;
-                let ny = y as i32 + dy;
+        search.first_step(target).unwrap_or((0, 0)) // ✅ Stay in place if Pac-Man can't be reached
+    }
 
-                if nx >= 0 && nx < WIDTH as i32 && ny >= 0 && ny < HEIGHT as i32 {
-                    let nx = nx as usize;
-                    let ny = ny as usize;
+    /// Holds a direction until the next call; `(0, 0)` lets go.
+    pub fn change_direction(&mut self, dx: i32, dy: i32) {
+        self.held = (dx, dy);
+        self.steer();
+    }
 
-                    if self.grid[ny][nx] != 1 && !visited[ny][nx] {
-                        queue.push_back((nx, ny));
-                        visited[ny][nx] = true;
-                        parent[ny][nx] = Some((x, y));
-                    }
-                }
+    fn steer(&mut self) {
+        let (dx, dy) = self.held;
+        if dx == 0 && dy == 0 {
+            self.stopped = true;  // ✅ Stop Pac-Man when no key is pressed
+        } else {
+            self.next_direction = (dx, dy);
+            self.direction = (dx, dy); // ✅ Update direction immediately
+            self.stopped = false;      // ✅ Resume movement when key is pressed
+            if self.waiting {
+                self.waiting = false;
+                self.events.push(GameEvent::Started);
             }
         }
+    }
 
-        // ✅ 30% chance to make a wrong turn, but only to a valid tile
-        if rng.gen_range(0..100) < 30 {
-            let mut shuffled_directions = directions.to_vec();
-            shuffled_directions.shuffle(&mut rng); // Randomize choices
+    pub fn reset(&mut self) {
+        self.pacman = PACMAN_START;
+        self.direction = (0, 0);
+        self.next_direction = (0, 0);
+        self.stopped = true;
+        self.held = (0, 0); // ✅ A new run waits for a fresh key press
+        self.waiting = true;
+        self.last_pacman_update = 0.0;
+        self.last_ghost_update = 0.0;
+
+        self.ghosts = GHOST_STARTS.to_vec();
+        self.ghost_directions = GHOST_START_DIRECTIONS.to_vec();
+        self.previous_pacman = PACMAN_START;
+        self.previous_ghosts = GHOST_STARTS.to_vec();
+
+        // ✅ Fresh maze and pellets from the run's seed
+        self.seed = rng::run_seed(&self.game_config);
+        self.rng = rng::seeded(self.seed);
+        self.score = 0;
+        self.new_stage();
+    }
 
-            for &(dx, dy) in &shuffled_directions {
-                let new_x = ghost.0 as i32 + dx;
-                let new_y = ghost.1 as i32 + dy;
+    /// Steers player 2's ghost in a two-player game; `(0, 0)` stops it.
+    pub fn change_ghost_direction(&mut self, dx: i32, dy: i32) {
+        self.ghost_input = (dx, dy);
+    }
 
-                if new_x >= 0 && new_x < WIDTH as i32 && new_y >= 0 && new_y < HEIGHT as i32 {
-                    let new_x = new_x as usize;
-                    let new_y = new_y as usize;
+    /// No run going: before the first input and after a game over.
+    pub fn waiting(&self) -> bool {
+        self.waiting
+    }
 
-                    if self.grid[new_y][new_x] != 1 { // ✅ Ensure it's not a wall
-                        return (dx, dy);
-                    }
-                }
-            }
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    pub fn challenge_token(&self) -> Option<String> {
+        self.last_run
+            .map(|(seed, score)| challenge::encode(&self.game_config, seed, score))
+    }
+
+    /// Events since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn view(&self) -> PacmanView {
+        let rows = self.grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&cell| match cell {
+                        1 => '#',
+                        2 => '.',
+                        3 => 'o',
+                        _ => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        PacmanView {
+            rows,
+            pacman: self.pacman,
+            ghosts: self.ghosts.clone(),
+            frightened: self.frightened > 0.0,
+            controlled_ghost: self.controlled_ghost,
+            hud: Hud {
+                scores: vec![self.score],
+                result: if self.waiting { self.last_result } else { None },
+                challenge_score: self.game_config.challenge_score,
+            },
         }
+    }
 
-        // ✅ Find the first step in the shortest path
-        let mut step = target;
-        while let Some(prev) = parent[step.1][step.0] {
-            if prev == ghost {
-                let move_x = step.0 as i32 - ghost.0 as i32;
-                let move_y = step.1 as i32 - ghost.1 as i32;
-
-                // ✅ Ensure the ghost does not move onto a wall
-                let next_x = ghost.0 as i32 + move_x;
-                let next_y = ghost.1 as i32 + move_y;
-                if next_x >= 0 && next_x < WIDTH as i32 && next_y >= 0 && next_y < HEIGHT as i32 {
-                    let next_x = next_x as usize;
-                    let next_y = next_y as usize;
-
-                    if self.grid[next_y][next_x] != 1 {
-                        return (move_x, move_y);
+    /// Pac-Man's view for `env.rs`. Features: his position, the walls around
+    /// him, the way to the nearest pellet, power pellet time left and where
+    /// each ghost is relative to him. Grid: walls, pellets, power pellets,
+    /// Pac-Man, ghosts, frightened ghosts.
+    pub(crate) fn observe(&self, kind: ObservationKind) -> Observation {
+        let (px, py) = self.pacman;
+
+        match kind {
+            ObservationKind::Features => {
+                let mut features = vec![px as f32 / WIDTH as f32, py as f32 / HEIGHT as f32];
+                features.extend([(0, -1), (0, 1), (-1, 0), (1, 0)].map(|(dx, dy): (i32, i32)| {
+                    (self.grid[(py as i32 + dy) as usize][(px as i32 + dx) as usize] == 1) as u8 as f32
+                }));
+
+                let nearest = (0..HEIGHT)
+                    .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+                    .filter(|&(x, y)| matches!(self.grid[y][x], 2 | 3))
+                    .min_by_key(|&(x, y)| x.abs_diff(px) + y.abs_diff(py))
+                    .unwrap_or(self.pacman);
+                features.push((nearest.0 as f32 - px as f32) / WIDTH as f32);
+                features.push((nearest.1 as f32 - py as f32) / HEIGHT as f32);
+                features.push((self.frightened / POWER_PELLET_DURATION) as f32);
+
+                for &(gx, gy) in &self.ghosts {
+                    features.push((gx as f32 - px as f32) / WIDTH as f32);
+                    features.push((gy as f32 - py as f32) / HEIGHT as f32);
+                }
+                Observation::features(features)
+            }
+            ObservationKind::Grid => {
+                let mut grid = Observation::grid(6, WIDTH, HEIGHT);
+                for (y, row) in self.grid.iter().enumerate() {
+                    for (x, &cell) in row.iter().enumerate() {
+                        match cell {
+                            1 => grid.set(0, x, y),
+                            2 => grid.set(1, x, y),
+                            3 => grid.set(2, x, y),
+                            _ => {}
+                        }
                     }
                 }
+                grid.set(3, px, py);
+                let ghost_layer = if self.frightened > 0.0 { 5 } else { 4 };
+                for &(gx, gy) in &self.ghosts {
+                    grid.set(ghost_layer, gx, gy);
+                }
+                grid
             }
-            step = prev;
         }
+    }
 
-        (0, 0) // No valid move found (stay in place)
+    /// Where Pac-Man and the ghosts were before their last moves, and how far
+    /// a frame drawn `alpha` of a tick after the last update is into the next.
+    pub(crate) fn motion(&self, alpha: f64) -> PacmanMotion {
+        let ahead = alpha * TICK;
+        PacmanMotion {
+            pacman: self.previous_pacman,
+            ghosts: self.previous_ghosts.clone(),
+            pacman_progress: ((self.last_pacman_update + ahead) / self.config.pacman_move_interval).min(1.0),
+            ghost_progress: ((self.last_ghost_update + ahead) / self.config.ghost_move_interval).min(1.0),
+        }
     }
 
-    #[wasm_bindgen]
-    pub fn render(&self) {
-        self.context.set_fill_style(&"black".into());
-        self.context.fill_rect(0.0, 0.0, (WIDTH as f64) * GRID_SIZE, (HEIGHT as f64) * GRID_SIZE);
-
-        // Draw walls, pellets
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                match self.grid[y][x] {
-                    1 => {
-                        self.context.set_fill_style(&"blue".into()); // Wall
-                        self.context.fill_rect(
-                            (x as f64) * GRID_SIZE,
-                            (y as f64) * GRID_SIZE,
-                            GRID_SIZE,
-                            GRID_SIZE,
-                        );
-                    }
-                    2 => {
-                        self.context.set_fill_style(&"white".into()); // Pellet
-                        self.context.begin_path();
-                        self.context
-                            .arc(
-                                (x as f64 + 0.5) * GRID_SIZE,
-                                (y as f64 + 0.5) * GRID_SIZE,
-                                3.0,
-                                0.0,
-                                std::f64::consts::PI * 2.0,
-                            )
-                            .unwrap();
-                        self.context.fill();
-                    }
-                    _ => {}
+    fn game_over(&mut self, winner: Option<usize>) {
+        if self.controlled_ghost.is_some() {
+            self.last_result = Some(RunResult::Winner(winner));
+        } else {
+            self.last_run = Some((self.seed, self.score));
+            self.last_result = Some(RunResult::Score(self.score));
+        }
+        self.events.push(GameEvent::GameOver { score: self.score });
+        self.reset();
+    }
+}
+
+/// What `draw` needs to slide Pac-Man and the ghosts between tiles.
+pub(crate) struct PacmanMotion {
+    pacman: (usize, usize),
+    ghosts: Vec<(usize, usize)>,
+    pacman_progress: f64,
+    ghost_progress: f64,
+}
+
+/// Draws `view`, with Pac-Man and the ghosts part of the way from their
+/// previous tiles when there's a `motion`.
+pub(crate) fn draw(context: &dyn Canvas, view: &PacmanView, motion: Option<&PacmanMotion>, style: &Style) {
+    let palette = style.palette();
+    context.set_fill_style_str(palette.background);
+    context.fill_rect(0.0, 0.0, (WIDTH as f64) * GRID_SIZE, (HEIGHT as f64) * GRID_SIZE);
+
+    // Draw walls, pellets
+    for (y, row) in view.rows.iter().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            match cell {
+                '#' => {
+                    context.set_fill_style_str(palette.walls);
+                    context.fill_rect(
+                        (x as f64) * GRID_SIZE,
+                        (y as f64) * GRID_SIZE,
+                        GRID_SIZE,
+                        GRID_SIZE,
+                    );
                 }
+                '.' | 'o' => {
+                    context.set_fill_style_str(palette.pellets); // ✅ Bigger for power pellets
+                    context.begin_path();
+                    context
+                        .arc(
+                            (x as f64 + 0.5) * GRID_SIZE,
+                            (y as f64 + 0.5) * GRID_SIZE,
+                            if cell == 'o' { 6.0 } else { 3.0 },
+                            0.0,
+                            std::f64::consts::PI * 2.0,
+                        )
+                        .unwrap();
+                    context.fill();
+                }
+                _ => {}
             }
         }
+    }
 
-        // ✅ Draw Pac-Man
-        self.context.set_fill_style(&"yellow".into());
-        self.context.begin_path();
-        self.context
+    // ✅ Draw Pac-Man
+    let (px, py) = match motion {
+        Some(motion) => tween(motion.pacman, view.pacman, motion.pacman_progress),
+        None => (view.pacman.0 as f64, view.pacman.1 as f64),
+    };
+    context.set_fill_style_str(palette.pacman);
+    context.begin_path();
+    context
+        .arc(
+            (px + 0.5) * GRID_SIZE,
+            (py + 0.5) * GRID_SIZE,
+            GRID_SIZE / 2.0,
+            0.2,
+            std::f64::consts::PI * 1.8,
+        )
+        .unwrap();
+    context.fill();
+
+    // ✅ Ghosts in Blinky, Pinky, Inky, Clyde order
+    for (i, &(gx, gy)) in view.ghosts.iter().enumerate() {
+        let (gx, gy) = match motion.and_then(|motion| Some((*motion.ghosts.get(i)?, motion.ghost_progress))) {
+            Some((from, progress)) => tween(from, (gx, gy), progress),
+            None => (gx as f64, gy as f64),
+        };
+        let color = if view.frightened {
+            palette.frightened // ✅ Frightened ghosts turn blue
+        } else {
+            palette.ghosts[i % palette.ghosts.len()]
+        };
+
+        context.set_fill_style_str(color);
+        context.begin_path();
+        context
             .arc(
-                (self.pacman.0 as f64 + 0.5) * GRID_SIZE,
-                (self.pacman.1 as f64 + 0.5) * GRID_SIZE,
+                (gx + 0.5) * GRID_SIZE,
+                (gy + 0.5) * GRID_SIZE,
                 GRID_SIZE / 2.0,
-                0.2,
-                std::f64::consts::PI * 1.8,
+                0.0,
+                std::f64::consts::PI * 2.0,
             )
             .unwrap();
-        self.context.fill();
-
-        // ✅ Updated Ghost Rendering with Classic Colors
-        let ghost_colors = ["#FF0000", "#FFC0CB", "#00FFFF", "#FFA500"]; // Blinky, Pinky, Inky, Clyde
-
-        for (i, &(gx, gy)) in self.ghosts.iter().enumerate() {
-            let color = ghost_colors[i % ghost_colors.len()]; // Assign colors in order
-
-            self.context.set_fill_style(&color.into());
-            self.context.begin_path();
-            self.context
-                .arc(
-                    (gx as f64 + 0.5) * GRID_SIZE,
-                    (gy as f64 + 0.5) * GRID_SIZE,
-                    GRID_SIZE / 2.0,
-                    0.0,
-                    std::f64::consts::PI * 2.0,
-                )
-                .unwrap();
-            self.context.fill();
+        context.fill();
+
+        if view.controlled_ghost == Some(i) {
+            context.set_stroke_style_str(palette.text); // ✅ Mark player 2's ghost
+            context.set_line_width(2.0);
+            context.stroke();
+        }
+        if style.cues {
+            let center = ((gx + 0.5) * GRID_SIZE, (gy + 0.5) * GRID_SIZE);
+            let letter = GHOST_LETTERS[i % GHOST_LETTERS.len()];
+            game::draw_cue(context, letter, center, GRID_SIZE * 0.6, palette.background);
         }
     }
 
+    game::draw_hud(context, (WIDTH as f64) * GRID_SIZE, (HEIGHT as f64) * GRID_SIZE, &view.hud, style);
+}
+
+fn tween(from: (usize, usize), to: (usize, usize), progress: f64) -> (f64, f64) {
+    view::lerp_cell((from.0 as f64, from.1 as f64), (to.0 as f64, to.1 as f64), progress)
+}
+
+#[wasm_bindgen]
+pub struct PacmanGame {
+    sim: PacmanSim,
+    context: CanvasRenderingContext2d,
+    achievements: Achievements,
+    announcer: Announcer,
+    recorder: Recorder,
+    style: Style,
+}
+
+#[wasm_bindgen]
+impl PacmanGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas: HtmlCanvasElement) -> PacmanGame {
+        Self::with_config(canvas, &GameConfig::new(GameKind::Pacman))
+    }
+
     #[wasm_bindgen]
-    pub fn change_direction(&mut self, dx: i32, dy: i32) {
-        if dx == 0 && dy == 0 {
-            self.stopped = true;  // ✅ Stop Pac-Man when no key is pressed
-        } else {
-            self.next_direction = (dx, dy);
-            self.direction = (dx, dy); // ✅ Update direction immediately
-            self.stopped = false;      // ✅ Resume movement when key is pressed
+    pub fn with_config(canvas: HtmlCanvasElement, game_config: &GameConfig) -> PacmanGame {
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+
+        PacmanGame {
+            sim: PacmanSim::new(game_config),
+            context,
+            achievements: Achievements::load(),
+            announcer: Announcer::new(GameKind::Pacman),
+            recorder: Recorder::new(),
+            style: game_config.style(),
         }
     }
 
+    #[wasm_bindgen]
+    pub fn update(&mut self, delta_time: f64) {
+        self.achievements.update(delta_time);
+        self.sim.update(delta_time);
+        self.record_events();
+        self.recorder.tick();
+    }
+
+    #[wasm_bindgen]
+    pub fn render(&self) {
+        self.draw_frame(None);
+    }
+
+    /// Draws Pac-Man and the ghosts `alpha` (0 to 1) of a tick past the last
+    /// update, so they glide between tiles on displays faster than the tick rate.
+    #[wasm_bindgen]
+    pub fn render_at(&self, alpha: f64) {
+        self.draw_frame(Some(&self.sim.motion(alpha)));
+    }
+
+    #[wasm_bindgen]
+    pub fn change_direction(&mut self, dx: i32, dy: i32) {
+        self.command(Command::Steer(0, dx, dy));
+    }
+
     #[wasm_bindgen]
     pub fn reset(&mut self) {
-        self.pacman = (14, 23);
-        self.direction = (0, 0);
-        self.next_direction = (0, 0);
-        self.stopped = true;
+        self.sim.reset();
+        self.recorder.cancel();
+    }
 
-        self.ghosts = vec![(13, 11), (14, 11), (15, 11), (16, 11)];
-        self.ghost_directions = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
+    /// Steers player 2's ghost in a two-player game; `(0, 0)` stops it.
+    #[wasm_bindgen]
+    pub fn change_ghost_direction(&mut self, dx: i32, dy: i32) {
+        self.command(Command::Steer(1, dx, dy));
+    }
 
-        // Restore pellets
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                if self.grid[y][x] != 1 {
-                    self.grid[y][x] = 2;
-                }
-            }
+    #[wasm_bindgen]
+    pub fn score(&self) -> u32 {
+        self.sim.score()
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    #[wasm_bindgen]
+    pub fn challenge_token(&self) -> Option<String> {
+        self.sim.challenge_token()
+    }
+
+    /// JSON input replay of the last finished solo run, for the leaderboard.
+    #[wasm_bindgen]
+    pub fn replay(&self) -> Option<String> {
+        self.recorder.last().map(|replay| serde_json::to_string(replay).expect("replays serialize"))
+    }
+
+    /// Solo runs finished so far; when it changes there is a new challenge
+    /// token and replay to offer.
+    #[wasm_bindgen]
+    pub fn finished_runs(&self) -> u32 {
+        self.recorder.finished()
+    }
+}
+
+impl PacmanGame {
+    pub(crate) fn view(&self) -> View {
+        View::Pacman(self.sim.view())
+    }
+
+    pub(crate) fn waiting(&self) -> bool {
+        self.sim.waiting()
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Whether a bot is playing player 1, whose runs unlock no achievements.
+    pub(crate) fn set_bot(&mut self, bot: bool) {
+        self.achievements.set_bot(bot);
+    }
+
+    fn draw_frame(&self, motion: Option<&PacmanMotion>) {
+        draw(&self.context, &self.sim.view(), motion, &self.style);
+        self.achievements.render(&self.context, (WIDTH as f64) * GRID_SIZE, (HEIGHT as f64) * GRID_SIZE, &self.style);
+    }
+
+    fn record_events(&mut self) {
+        let events = self.sim.take_events();
+        self.recorder.events(&events, &self.sim);
+        for event in &events {
+            self.achievements.record(event);
         }
+        self.announcer.update(&events, &self.sim, self.style.strings());
+    }
+
+    pub(crate) fn command(&mut self, command: Command) {
+        Sim::command(&mut self.sim, command);
+        self.record_events();
+        self.recorder.command(command);
     }
 }
@@ -0,0 +1,252 @@
+//! Grid pathfinding shared by the Pac-Man ghosts and the bots (`agent.rs`).
+//! Tiles are `(x, y)`; moves are one tile up, down, left or right.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Up, down, left, right: the order every search tries moves in, so ties
+/// always break the same way and replays stay deterministic.
+pub(crate) const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// Breadth-first search over a grid: every tile reachable from the start,
+/// nearest first, with the first step of the shortest way there.
+pub(crate) struct Search {
+    width: usize,
+    pub(crate) order: Vec<(usize, usize)>,
+    first: Vec<Option<(i32, i32)>>,
+}
+
+impl Search {
+    /// `open` says which tiles can be entered.
+    pub(crate) fn new(
+        (width, height): (usize, usize),
+        start: (usize, usize),
+        open: impl Fn((usize, usize)) -> bool,
+    ) -> Search {
+        let mut search = Search { width, order: vec![start], first: vec![None; width * height] };
+        let mut seen = vec![false; width * height];
+        seen[start.1 * width + start.0] = true;
+
+        let mut i = 0;
+        while let Some(&(x, y)) = search.order.get(i) {
+            i += 1;
+            for next in neighbours((width, height), (x, y)) {
+                let index = next.1 * width + next.0;
+                if seen[index] || !open(next) {
+                    continue;
+                }
+                seen[index] = true;
+                let step = (next.0 as i32 - x as i32, next.1 as i32 - y as i32);
+                search.first[index] = if (x, y) == start { Some(step) } else { search.first[y * width + x] };
+                search.order.push(next);
+            }
+        }
+        search
+    }
+
+    /// First move of the shortest way to `tile`; `None` if it can't be reached
+    /// or is the start.
+    pub(crate) fn first_step(&self, (x, y): (usize, usize)) -> Option<(i32, i32)> {
+        self.first[y * self.width + x]
+    }
+
+    /// Tiles whose shortest way starts with `step`: the room on that side.
+    pub(crate) fn room(&self, step: (i32, i32)) -> usize {
+        self.first.iter().filter(|&&first| first == Some(step)).count()
+    }
+}
+
+/// A* from `start` to `goal` on Manhattan distance; the first move of the
+/// path found, `None` if there is none.
+pub(crate) fn a_star(
+    (width, height): (usize, usize),
+    start: (usize, usize),
+    goal: (usize, usize),
+    open: impl Fn((usize, usize)) -> bool,
+) -> Option<(i32, i32)> {
+    let index = |(x, y): (usize, usize)| y * width + x;
+    let estimate = |(x, y): (usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+
+    let mut cost = vec![usize::MAX; width * height];
+    let mut parent = vec![None; width * height];
+    let mut queue = BinaryHeap::new();
+    cost[index(start)] = 0;
+    queue.push(Reverse((estimate(start), 0, index(start))));
+
+    while let Some(Reverse((_, steps, i))) = queue.pop() {
+        let tile = (i % width, i / width);
+        if tile == goal {
+            break;
+        }
+        if steps > cost[i] {
+            continue; // ✅ Already reached more cheaply
+        }
+        for next in neighbours((width, height), tile) {
+            let j = index(next);
+            if steps + 1 < cost[j] && open(next) {
+                cost[j] = steps + 1;
+                parent[j] = Some(tile);
+                queue.push(Reverse((steps + 1 + estimate(next), steps + 1, j)));
+            }
+        }
+    }
+
+    // ✅ Walk back from the goal to the move out of the start
+    let mut tile = goal;
+    while let Some(previous) = parent[index(tile)] {
+        if previous == start {
+            return Some((tile.0 as i32 - start.0 as i32, tile.1 as i32 - start.1 as i32));
+        }
+        tile = previous;
+    }
+    None
+}
+
+/// Every tile of a `width`×`height` board once, each next to the one before
+/// and the last next to the first; `None` when both sides are odd and no
+/// such cycle exists. Along the top row, zigzag back over the rest, then up
+/// the first column.
+pub(crate) fn hamiltonian_cycle(width: usize, height: usize) -> Option<Vec<(usize, usize)>> {
+    if width % 2 == 1 && height % 2 == 1 {
+        return None;
+    }
+    if height % 2 == 1 {
+        let cycle = hamiltonian_cycle(height, width)?;
+        return Some(cycle.into_iter().map(|(x, y)| (y, x)).collect());
+    }
+    if width < 2 {
+        return None;
+    }
+
+    let mut cycle: Vec<_> = (0..width).map(|x| (x, 0)).collect();
+    for y in 1..height {
+        if y % 2 == 1 {
+            cycle.extend((1..width).rev().map(|x| (x, y)));
+        } else {
+            cycle.extend((1..width).map(|x| (x, y)));
+        }
+    }
+    cycle.extend((1..height).rev().map(|y| (0, y)));
+    Some(cycle)
+}
+
+/// In-bounds neighbours of `(x, y)`, in `DIRECTIONS` order.
+fn neighbours((width, height): (usize, usize), (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    DIRECTIONS.into_iter().filter_map(move |(dx, dy)| {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        (nx >= 0 && ny >= 0 && (nx as usize) < width && (ny as usize) < height).then_some((nx as usize, ny as usize))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// Walls: `#`. Start: `S`. Goal: `G`.
+    const MAZE: [&str; 7] = [
+        "S....#....",
+        ".###.#.##.",
+        "...#.#..#.",
+        "##.#.##.#.",
+        "...#....#G",
+        ".#####.##.",
+        "..........",
+    ];
+
+    fn find(maze: &[&str], tile: u8) -> (usize, usize) {
+        maze.iter()
+            .enumerate()
+            .find_map(|(y, row)| row.bytes().position(|b| b == tile).map(|x| (x, y)))
+            .unwrap()
+    }
+
+    /// Moves needed from `start` to `goal` by breadth-first search, if any way exists.
+    fn distance(maze: &[&str], start: (usize, usize), goal: (usize, usize)) -> Option<usize> {
+        let (width, height) = (maze[0].len(), maze.len());
+        let mut steps = vec![None; width * height];
+        steps[start.1 * width + start.0] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            let here = steps[y * width + x].unwrap();
+            for (dx, dy) in DIRECTIONS {
+                let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                if nx < 0 || ny < 0 || nx as usize >= width || ny as usize >= height {
+                    continue;
+                }
+                let (nx, ny) = (nx as usize, ny as usize);
+                if maze[ny].as_bytes()[nx] != b'#' && steps[ny * width + nx].is_none() {
+                    steps[ny * width + nx] = Some(here + 1);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        steps[goal.1 * width + goal.0]
+    }
+
+    /// Follows A*'s first step over and over; the moves it took to get to `goal`.
+    fn walk(maze: &[&str], start: (usize, usize), goal: (usize, usize)) -> Option<usize> {
+        let size = (maze[0].len(), maze.len());
+        let open = |(x, y): (usize, usize)| maze[y].as_bytes()[x] != b'#';
+        let (mut tile, mut moves) = (start, 0);
+        while tile != goal {
+            let (dx, dy) = a_star(size, tile, goal, open)?;
+            tile = ((tile.0 as i32 + dx) as usize, (tile.1 as i32 + dy) as usize);
+            assert!(open(tile), "walked into a wall at {:?}", tile);
+            moves += 1;
+        }
+        Some(moves)
+    }
+
+    #[test]
+    fn hamiltonian_cycle_visits_every_cell_once() {
+        for width in 1..=9 {
+            for height in 1..=9 {
+                let Some(cycle) = hamiltonian_cycle(width, height) else {
+                    assert!(width * height % 2 == 1 || width.min(height) < 2, "{}x{}", width, height);
+                    continue;
+                };
+
+                let mut seen = vec![false; width * height];
+                for &(x, y) in &cycle {
+                    assert!(x < width && y < height, "{}x{}: {:?} off the board", width, height, (x, y));
+                    assert!(!seen[y * width + x], "{}x{}: {:?} twice", width, height, (x, y));
+                    seen[y * width + x] = true;
+                }
+                assert_eq!(cycle.len(), width * height, "{}x{}", width, height);
+
+                // ✅ Each cell next to the one before, and round from the last to the first
+                for (i, &(x, y)) in cycle.iter().enumerate() {
+                    let (nx, ny) = cycle[(i + 1) % cycle.len()];
+                    assert_eq!(x.abs_diff(nx) + y.abs_diff(ny), 1, "{}x{}: {:?} to {:?}", width, height, (x, y), (nx, ny));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_star_takes_a_shortest_way_round_walls() {
+        let (start, goal) = (find(&MAZE, b'S'), find(&MAZE, b'G'));
+        assert_eq!(walk(&MAZE, start, goal), distance(&MAZE, start, goal));
+
+        // ✅ Every open tile to every other reachable one
+        let open: Vec<_> = (0..MAZE.len())
+            .flat_map(|y| (0..MAZE[0].len()).map(move |x| (x, y)))
+            .filter(|&(x, y)| MAZE[y].as_bytes()[x] != b'#')
+            .collect();
+        for &from in &open {
+            for &to in &open {
+                assert_eq!(walk(&MAZE, from, to), distance(&MAZE, from, to), "{:?} to {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn a_star_finds_nothing_behind_a_wall() {
+        let maze = ["..#..", "..#..", "..#.."];
+        let open = |(x, y): (usize, usize)| maze[y].as_bytes()[x] != b'#';
+        assert_eq!(a_star((5, 3), (0, 1), (4, 1), open), None);
+        assert_eq!(a_star((5, 3), (0, 1), (0, 1), open), None);
+    }
+}
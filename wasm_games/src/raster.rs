@@ -0,0 +1,352 @@
+//! A software rasterizer for recorded frames (`draw.rs`), so a seeded frame
+//! can be saved as a PNG without a browser, e.g. for a bug report:
+//!
+//! ```text
+//! cargo run --bin screenshot -- game=pacman seed=42 --ticks 600 --out maze.png
+//! ```
+//!
+//! Close to what a canvas draws, not pixel-identical: shapes are sampled at
+//! pixel centres without anti-aliasing and text uses a built-in 5×7 font.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::draw::{DisplayList, DrawCommand};
+use crate::theme::Style;
+use crate::view::View;
+
+type Rgba = [u8; 4];
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+/// Glyph plus spacing, in font pixels.
+const GLYPH_ADVANCE: f64 = 6.0;
+const DEFAULT_FONT_SIZE: f64 = 10.0;
+
+/// An RGBA image, row by row from the top left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// `view` drawn in `style` at its own canvas size.
+    pub fn render(view: &View, style: Style) -> Image {
+        let (width, height) = view.canvas_size();
+        Image::rasterize(&DisplayList::record(view, style).commands(), width, height)
+    }
+
+    /// Plays `commands` onto a transparent `width`×`height` image.
+    pub fn rasterize(commands: &[DrawCommand], width: u32, height: u32) -> Image {
+        let mut painter = Painter {
+            image: Image { width, height, pixels: vec![0; width as usize * height as usize * 4] },
+            fill: [0, 0, 0, 255],
+            stroke: [0, 0, 0, 255],
+            line_width: 1.0,
+            font_size: DEFAULT_FONT_SIZE,
+            text_align: TextAlign::Start,
+            path: Vec::new(),
+        };
+        for command in commands {
+            painter.apply(command);
+        }
+        painter.image
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Rgba {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+
+    /// Blends `color` over every pixel `covered` says is inside the shape.
+    fn paint(&mut self, color: Rgba, covered: impl Fn(f64, f64) -> bool, (x0, y0, x1, y1): (f64, f64, f64, f64)) {
+        let clamp = |value: f64, max: u32| value.max(0.0).min(max as f64) as u32;
+        for y in clamp(y0.floor(), self.height)..clamp(y1.ceil(), self.height) {
+            for x in clamp(x0.floor(), self.width)..clamp(x1.ceil(), self.width) {
+                if covered(x as f64 + 0.5, y as f64 + 0.5) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Source-over, like the canvas default.
+    fn blend(&mut self, x: u32, y: u32, [r, g, b, a]: Rgba) {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let alpha = a as f64 / 255.0;
+        let below = self.pixels[i + 3] as f64 / 255.0;
+        let out = alpha + below * (1.0 - alpha);
+        for (channel, value) in [r, g, b].into_iter().enumerate() {
+            let mixed = value as f64 * alpha + self.pixels[i + channel] as f64 * below * (1.0 - alpha);
+            self.pixels[i + channel] = if out > 0.0 { (mixed / out).round() as u8 } else { 0 };
+        }
+        self.pixels[i + 3] = (out * 255.0).round() as u8;
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TextAlign {
+    Start,
+    Center,
+    End,
+}
+
+/// Canvas state while playing back a display list.
+struct Painter {
+    image: Image,
+    fill: Rgba,
+    stroke: Rgba,
+    line_width: f64,
+    font_size: f64,
+    text_align: TextAlign,
+    /// The current path as one polyline; `arc` joins on like the canvas does.
+    path: Vec<(f64, f64)>,
+}
+
+impl Painter {
+    fn apply(&mut self, command: &DrawCommand) {
+        match command {
+            // ✅ Like the canvas, colours it can't parse are ignored
+            DrawCommand::FillStyle(color) => self.fill = parse_color(color).unwrap_or(self.fill),
+            DrawCommand::StrokeStyle(color) => self.stroke = parse_color(color).unwrap_or(self.stroke),
+            DrawCommand::LineWidth(width) => self.line_width = *width,
+            DrawCommand::Font(font) => self.font_size = parse_font_size(font).unwrap_or(self.font_size),
+            DrawCommand::TextAlign(align) => {
+                self.text_align = match align.as_str() {
+                    "center" => TextAlign::Center,
+                    "right" | "end" => TextAlign::End,
+                    _ => TextAlign::Start,
+                }
+            }
+            &DrawCommand::FillRect { x, y, width, height } => {
+                let (x0, x1) = (x.min(x + width), x.max(x + width));
+                let (y0, y1) = (y.min(y + height), y.max(y + height));
+                let inside = |px: f64, py: f64| px >= x0 && px < x1 && py >= y0 && py < y1;
+                self.image.paint(self.fill, inside, (x0, y0, x1, y1));
+            }
+            DrawCommand::BeginPath => self.path.clear(),
+            &DrawCommand::Arc { x, y, radius, start_angle, end_angle } => {
+                let sweep = end_angle - start_angle;
+                let segments = (sweep.abs() * radius).ceil().max(8.0) as usize;
+                self.path.extend((0..=segments).map(|i| {
+                    let angle = start_angle + sweep * i as f64 / segments as f64;
+                    (x + radius * angle.cos(), y + radius * angle.sin())
+                }));
+            }
+            DrawCommand::Fill => self.fill_path(),
+            DrawCommand::Stroke => self.stroke_path(),
+            DrawCommand::FillText { text, x, y } => self.fill_text(text, *x, *y),
+        }
+    }
+
+    /// Even-odd fill of the path, closed back to its start.
+    fn fill_path(&mut self) {
+        let Some(bounds) = bounds(&self.path, 0.0) else {
+            return;
+        };
+        let path = &self.path;
+        let inside = |px: f64, py: f64| {
+            let mut inside = false;
+            for (i, &(x0, y0)) in path.iter().enumerate() {
+                let (x1, y1) = path[(i + 1) % path.len()];
+                if (y0 <= py) != (y1 <= py) && px < x0 + (py - y0) / (y1 - y0) * (x1 - x0) {
+                    inside = !inside;
+                }
+            }
+            inside
+        };
+        self.image.paint(self.fill, inside, bounds);
+    }
+
+    /// Every pixel within half the line width of the path.
+    fn stroke_path(&mut self) {
+        let half = self.line_width / 2.0;
+        let Some(bounds) = bounds(&self.path, half) else {
+            return;
+        };
+        let path = &self.path;
+        let near = |px: f64, py: f64| {
+            path.windows(2).any(|segment| distance_to_segment((px, py), segment[0], segment[1]) <= half)
+        };
+        self.image.paint(self.stroke, near, bounds);
+    }
+
+    /// `y` is the baseline; glyphs are scaled up from the 5×7 font in whole
+    /// pixels to about the font size.
+    fn fill_text(&mut self, text: &str, x: f64, y: f64) {
+        let scale = (self.font_size / 8.0).round().max(1.0);
+        let width = text.chars().count() as f64 * GLYPH_ADVANCE * scale - scale;
+        let left = match self.text_align {
+            TextAlign::Start => x,
+            TextAlign::Center => x - width / 2.0,
+            TextAlign::End => x - width,
+        }
+        .round();
+        let top = (y - GLYPH_HEIGHT as f64 * scale).round();
+
+        for (i, c) in text.chars().enumerate() {
+            let Some(rows) = glyph(c) else {
+                continue;
+            };
+            let glyph_left = left + i as f64 * GLYPH_ADVANCE * scale;
+            let lit = |px: f64, py: f64| {
+                let column = ((px - glyph_left) / scale) as usize;
+                let row = ((py - top) / scale) as usize;
+                row < GLYPH_HEIGHT && column < GLYPH_WIDTH && rows[row] & (1 << (GLYPH_WIDTH - 1 - column)) != 0
+            };
+            let bounds = (glyph_left, top, glyph_left + GLYPH_WIDTH as f64 * scale, top + GLYPH_HEIGHT as f64 * scale);
+            self.image.paint(self.fill, lit, bounds);
+        }
+    }
+}
+
+/// Bounding box of `points`, grown by `margin`.
+fn bounds(points: &[(f64, f64)], margin: f64) -> Option<(f64, f64, f64, f64)> {
+    let &(x, y) = points.first()?;
+    let (x0, y0, x1, y1) = points.iter().fold((x, y, x, y), |(x0, y0, x1, y1), &(x, y)| {
+        (x0.min(x), y0.min(y), x1.max(x), y1.max(y))
+    });
+    Some((x0 - margin, y0 - margin, x1 + margin, y1 + margin))
+}
+
+fn distance_to_segment((px, py): (f64, f64), (x0, y0): (f64, f64), (x1, y1): (f64, f64)) -> f64 {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 { 0.0 } else { (((px - x0) * dx + (py - y0) * dy) / length).clamp(0.0, 1.0) };
+    ((px - x0 - t * dx).powi(2) + (py - y0 - t * dy).powi(2)).sqrt()
+}
+
+/// Named colours the games use, `#rgb`, `#rrggbb`, `rgb(...)` and `rgba(...)`.
+fn parse_color(color: &str) -> Option<Rgba> {
+    let color = color.trim().to_ascii_lowercase();
+    let named = match color.as_str() {
+        "black" => Some([0, 0, 0, 255]),
+        "white" => Some([255, 255, 255, 255]),
+        "red" => Some([255, 0, 0, 255]),
+        "green" => Some([0, 128, 0, 255]),
+        "blue" => Some([0, 0, 255, 255]),
+        "yellow" => Some([255, 255, 0, 255]),
+        "gold" => Some([255, 215, 0, 255]),
+        "orange" => Some([255, 165, 0, 255]),
+        "gray" | "grey" => Some([128, 128, 128, 255]),
+        "transparent" => Some([0, 0, 0, 0]),
+        _ => None,
+    };
+    if named.is_some() {
+        return named;
+    }
+
+    if let Some(hex) = color.strip_prefix('#') {
+        let digits: Vec<u8> = hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect::<Option<_>>()?;
+        return match digits[..] {
+            [r, g, b] => Some([r * 17, g * 17, b * 17, 255]),
+            [r1, r0, g1, g0, b1, b0] => Some([r1 * 16 + r0, g1 * 16 + g0, b1 * 16 + b0, 255]),
+            _ => None,
+        };
+    }
+
+    let inner = color.strip_prefix("rgba(").or_else(|| color.strip_prefix("rgb("))?.strip_suffix(')')?;
+    let parts: Vec<f64> = inner.split(',').map(|part| part.trim().parse().ok()).collect::<Option<_>>()?;
+    let channel = |value: f64| value.clamp(0.0, 255.0).round() as u8;
+    match parts[..] {
+        [r, g, b] => Some([channel(r), channel(g), channel(b), 255]),
+        [r, g, b, a] => Some([channel(r), channel(g), channel(b), channel(a * 255.0)]),
+        _ => None,
+    }
+}
+
+/// The pixel size in a CSS font like `16px monospace`.
+fn parse_font_size(font: &str) -> Option<f64> {
+    font.split_whitespace().find_map(|part| part.strip_suffix("px")?.parse().ok())
+}
+
+/// Rows of a 5×7 glyph, top first, leftmost pixel in bit 4. Letters are all
+/// drawn as capitals, accented ones without their accent; characters without
+/// a glyph leave a gap.
+fn glyph(c: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    let rows = match unaccented(c).to_ascii_uppercase() {
+        ' ' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        '\'' => [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '?' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+/// The letter under an accent in Spanish and Portuguese text, and `¡`/`¿`
+/// as `!`/`?`, so translated text stays readable at 5×7.
+fn unaccented(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ã' | 'Á' | 'À' | 'Â' | 'Ã' => 'A',
+        'é' | 'ê' | 'É' | 'Ê' => 'E',
+        'í' | 'Í' => 'I',
+        'ó' | 'ô' | 'õ' | 'Ó' | 'Ô' | 'Õ' => 'O',
+        'ú' | 'ü' | 'Ú' | 'Ü' => 'U',
+        'ñ' | 'Ñ' => 'N',
+        'ç' | 'Ç' => 'C',
+        '¡' => '!',
+        '¿' => '?',
+        c => c,
+    }
+}
@@ -0,0 +1,201 @@
+//! Input replays of finished solo runs, and checking one by playing it back.
+//!
+//! A replay is the run's challenge token (settings, seed and claimed score,
+//! see `challenge.rs`) plus every command with the tick it was given on. Ticks
+//! count fixed `lockstep::TICK` updates from the command that started the run,
+//! so playing the commands back through the same sim ends the same way.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::challenge;
+use crate::config::{ConfigError, GameConfig};
+use crate::events::GameEvent;
+use crate::game::GameKind;
+use crate::lockstep::TICK;
+use crate::sim::{self, Command, Sim};
+
+/// Longest run a replay may take to end: an hour of play.
+pub const MAX_TICKS: u32 = 60 * 60 * 60;
+/// How long a run may go on after its last input: five minutes, when the
+/// slowest snake crosses the widest board in well under one and idle ghosts
+/// catch Pac-Man in two or three.
+pub const END_GRACE: u32 = 60 * 60 * 5;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub challenge: String,
+    pub inputs: Vec<(u32, Command)>,
+}
+
+/// What a replay turned out to be when played back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verified {
+    pub config: GameConfig,
+    pub seed: u64,
+    pub score: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReplayError {
+    Challenge(ConfigError),
+    OutOfOrder { tick: u32 },
+    /// A command for a player the run doesn't have, or one its game doesn't take.
+    BadInput { tick: u32 },
+    /// An input for a tick after the run had already ended.
+    AfterEnd { tick: u32 },
+    TooLong,
+    /// The inputs for the first tick don't start a run.
+    NeverStarted,
+    /// Still going `END_GRACE` ticks after the last input.
+    NeverEnded,
+    ScoreMismatch { claimed: u32, actual: u32 },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Challenge(err) => write!(f, "bad challenge token: {}", err),
+            ReplayError::OutOfOrder { tick } => write!(f, "input for tick {} is out of order", tick),
+            ReplayError::BadInput { tick } => write!(f, "input for tick {} isn't a move in this game", tick),
+            ReplayError::AfterEnd { tick } => write!(f, "input for tick {} comes after the run ended", tick),
+            ReplayError::TooLong => write!(f, "replay is longer than {} ticks", MAX_TICKS),
+            ReplayError::NeverStarted => write!(f, "the first input doesn't start the run"),
+            ReplayError::NeverEnded => write!(f, "the run goes on for {} ticks after the last input", END_GRACE),
+            ReplayError::ScoreMismatch { claimed, actual } => {
+                write!(f, "claimed a score of {} but the replay scores {}", claimed, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<ConfigError> for ReplayError {
+    fn from(err: ConfigError) -> ReplayError {
+        ReplayError::Challenge(err)
+    }
+}
+
+impl Replay {
+    /// Plays the inputs back and checks the run ends with the claimed score,
+    /// and with no inputs left over. Every input has to be one the game could
+    /// have acted on, so a run can't be sent again padded with no-ops. Play
+    /// stops `END_GRACE` ticks after the last input, so a replay that never
+    /// ends the run costs that much rather than all of `MAX_TICKS`.
+    pub fn verify(&self) -> Result<Verified, ReplayError> {
+        let (config, claimed) = challenge::decode(&self.challenge)?;
+        let seed = config.seed().expect("challenge tokens carry a seed");
+
+        if let Some(window) = self.inputs.windows(2).find(|pair| pair[1].0 < pair[0].0) {
+            return Err(ReplayError::OutOfOrder { tick: window[1].0 });
+        }
+        let takes = |command: Command| match (config.game(), command) {
+            (GameKind::Flappy, Command::Flap(player)) => player < config.players(),
+            (GameKind::Snake, Command::Steer(player, dx, dy)) => player < config.players() && dx.abs() + dy.abs() == 1,
+            (GameKind::Pacman, Command::Steer(player, dx, dy)) => player < config.players() && dx.abs() + dy.abs() <= 1,
+            _ => false,
+        };
+        if let Some(&(tick, _)) = self.inputs.iter().find(|&&(_, command)| !takes(command)) {
+            return Err(ReplayError::BadInput { tick });
+        }
+        if self.inputs.last().is_some_and(|&(tick, _)| tick >= MAX_TICKS) {
+            return Err(ReplayError::TooLong);
+        }
+
+        let mut sim = sim::new_sim(&config);
+        let mut inputs = self.inputs.iter().peekable();
+        let last = self.inputs.last().map_or(0, |&(tick, _)| tick);
+        for tick in 0..last.saturating_add(END_GRACE).min(MAX_TICKS) {
+            while let Some((_, command)) = inputs.next_if(|&&(at, _)| at == tick) {
+                sim.command(*command);
+            }
+            if tick == 0 && sim.waiting() {
+                return Err(ReplayError::NeverStarted);
+            }
+            sim.update(TICK);
+
+            let over = sim.take_events().into_iter().find_map(|event| match event {
+                GameEvent::GameOver { score } => Some(score),
+                _ => None,
+            });
+            if let Some(score) = over {
+                if let Some(&&(tick, _)) = inputs.peek() {
+                    return Err(ReplayError::AfterEnd { tick });
+                }
+                return if score == claimed {
+                    Ok(Verified { config, seed, score })
+                } else {
+                    Err(ReplayError::ScoreMismatch { claimed, actual: score })
+                };
+            }
+        }
+
+        Err(ReplayError::NeverEnded)
+    }
+}
+
+/// Records the commands of each solo run as it's played.
+#[derive(Default)]
+pub struct Recorder {
+    tick: u32,
+    inputs: Option<Vec<(u32, Command)>>, // ✅ `None` between runs
+    last: Option<Replay>,
+    finished: u32,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    /// The last finished solo run.
+    pub fn last(&self) -> Option<&Replay> {
+        self.last.as_ref()
+    }
+
+    /// How many runs have finished, counting each replay `last` has held.
+    pub fn finished(&self) -> u32 {
+        self.finished
+    }
+
+    /// Call after the sim took `command` and its events went to `events`.
+    pub fn command(&mut self, command: Command) {
+        if let Some(inputs) = &mut self.inputs {
+            inputs.push((self.tick, command));
+        }
+    }
+
+    /// Starts and finishes runs; call with every batch of the sim's events.
+    pub fn events(&mut self, events: &[GameEvent], sim: &dyn Sim) {
+        for event in events {
+            match event {
+                GameEvent::Started => {
+                    self.tick = 0;
+                    self.inputs = Some(Vec::new());
+                }
+                GameEvent::GameOver { .. } => {
+                    let inputs = self.inputs.take();
+                    if let (Some(inputs), Some(challenge)) = (inputs, sim.challenge_token()) {
+                        self.last = Some(Replay { challenge, inputs });
+                        self.finished += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Call after every fixed update.
+    pub fn tick(&mut self) {
+        if self.inputs.is_some() {
+            self.tick += 1;
+        }
+    }
+
+    /// Drops the run in progress, e.g. when the game is reset from outside.
+    pub fn cancel(&mut self) {
+        self.inputs = None;
+    }
+}
@@ -0,0 +1,20 @@
+//! Seeded randomness, so a run can be replayed exactly from its seed.
+//!
+//! Ranges are drawn as `u32` and cast: `usize` ranges consume the stream
+//! differently on wasm32 and 64-bit native builds.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::config::GameConfig;
+
+pub type GameRng = StdRng;
+
+pub fn seeded(seed: u64) -> GameRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// The configured seed, or a fresh one for an unseeded game.
+pub fn run_seed(config: &GameConfig) -> u64 {
+    config.seed().unwrap_or_else(rand::random)
+}
@@ -0,0 +1,186 @@
+//! The three games behind one interface, for code that drives any of them
+//! without caring which: the canvas shell, replays, the leaderboard server.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::GameConfig;
+use crate::draw::Canvas;
+use crate::env::{Observation, ObservationKind};
+use crate::events::GameEvent;
+use crate::game::GameKind;
+use crate::theme::Style;
+use crate::view::View;
+use crate::{flappy, pacman, snake, FlappySim, PacmanSim, SnakeSim};
+
+/// A player's input, as the games take it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Command {
+    /// Flap `player`'s bird.
+    Flap(usize),
+    /// Turn `player`'s snake, or hold a direction in Pac-Man (`(0, 0)` lets go;
+    /// player 2 steers a ghost).
+    Steer(usize, i32, i32),
+}
+
+pub trait Sim {
+    fn command(&mut self, command: Command);
+    fn update(&mut self, delta_time: f64);
+    fn reset(&mut self);
+    /// Events since the last call, oldest first.
+    fn take_events(&mut self) -> Vec<GameEvent>;
+    fn scores(&self) -> Vec<u32>;
+    /// No run going: before the first input and after a game over.
+    fn waiting(&self) -> bool;
+    /// Token for a `?challenge=` link replaying the last finished solo run.
+    fn challenge_token(&self) -> Option<String>;
+    fn view(&self) -> View;
+    /// Draws the frame `alpha` (0 to 1) of a tick past the last update, with
+    /// whatever moved part of the way from where it was the tick before.
+    fn draw_at(&self, context: &dyn Canvas, alpha: f64, style: &Style);
+    /// Player 1's observation for `env.rs`.
+    fn observe(&self, kind: ObservationKind) -> Observation;
+}
+
+/// A fresh simulation of the configured game.
+pub fn new_sim(config: &GameConfig) -> Box<dyn Sim> {
+    match config.game() {
+        GameKind::Flappy => Box::new(FlappySim::new(config)),
+        GameKind::Snake => Box::new(SnakeSim::new(config)),
+        GameKind::Pacman => Box::new(PacmanSim::new(config)),
+    }
+}
+
+impl Sim for FlappySim {
+    fn command(&mut self, command: Command) {
+        if let Command::Flap(player) = command {
+            self.flap(player);
+        }
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        FlappySim::update(self, delta_time);
+    }
+
+    fn reset(&mut self) {
+        FlappySim::reset(self);
+    }
+
+    fn take_events(&mut self) -> Vec<GameEvent> {
+        FlappySim::take_events(self)
+    }
+
+    fn scores(&self) -> Vec<u32> {
+        FlappySim::scores(self)
+    }
+
+    fn waiting(&self) -> bool {
+        FlappySim::waiting(self)
+    }
+
+    fn challenge_token(&self) -> Option<String> {
+        FlappySim::challenge_token(self)
+    }
+
+    fn view(&self) -> View {
+        View::Flappy(FlappySim::view(self))
+    }
+
+    fn draw_at(&self, context: &dyn Canvas, alpha: f64, style: &Style) {
+        flappy::draw(context, &FlappySim::view(self), Some(&self.motion(alpha)), style);
+    }
+
+    fn observe(&self, kind: ObservationKind) -> Observation {
+        FlappySim::observe(self, kind)
+    }
+}
+
+impl Sim for SnakeSim {
+    fn command(&mut self, command: Command) {
+        if let Command::Steer(player, dx, dy) = command {
+            self.change_direction(player, dx, dy);
+        }
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        SnakeSim::update(self, delta_time);
+    }
+
+    fn reset(&mut self) {
+        SnakeSim::reset(self);
+    }
+
+    fn take_events(&mut self) -> Vec<GameEvent> {
+        SnakeSim::take_events(self)
+    }
+
+    fn scores(&self) -> Vec<u32> {
+        SnakeSim::scores(self)
+    }
+
+    fn waiting(&self) -> bool {
+        SnakeSim::waiting(self)
+    }
+
+    fn challenge_token(&self) -> Option<String> {
+        SnakeSim::challenge_token(self)
+    }
+
+    fn view(&self) -> View {
+        View::Snake(SnakeSim::view(self))
+    }
+
+    fn draw_at(&self, context: &dyn Canvas, alpha: f64, style: &Style) {
+        snake::draw(context, &SnakeSim::view(self), Some(&self.motion(alpha)), style);
+    }
+
+    fn observe(&self, kind: ObservationKind) -> Observation {
+        SnakeSim::observe(self, kind)
+    }
+}
+
+impl Sim for PacmanSim {
+    fn command(&mut self, command: Command) {
+        match command {
+            Command::Steer(0, dx, dy) => self.change_direction(dx, dy),
+            Command::Steer(_, dx, dy) => self.change_ghost_direction(dx, dy),
+            Command::Flap(_) => {}
+        }
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        PacmanSim::update(self, delta_time);
+    }
+
+    fn reset(&mut self) {
+        PacmanSim::reset(self);
+    }
+
+    fn take_events(&mut self) -> Vec<GameEvent> {
+        PacmanSim::take_events(self)
+    }
+
+    fn scores(&self) -> Vec<u32> {
+        vec![self.score()]
+    }
+
+    fn waiting(&self) -> bool {
+        PacmanSim::waiting(self)
+    }
+
+    fn challenge_token(&self) -> Option<String> {
+        PacmanSim::challenge_token(self)
+    }
+
+    fn view(&self) -> View {
+        View::Pacman(PacmanSim::view(self))
+    }
+
+    fn draw_at(&self, context: &dyn Canvas, alpha: f64, style: &Style) {
+        pacman::draw(context, &PacmanSim::view(self), Some(&self.motion(alpha)), style);
+    }
+
+    fn observe(&self, kind: ObservationKind) -> Observation {
+        PacmanSim::observe(self, kind)
+    }
+}
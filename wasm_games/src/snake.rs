@@ -1,116 +1,559 @@
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use std::f64;
+use rand::{Rng, RngCore};
 
-const GRID_SIZE: f64 = 20.0;
-const WIDTH: u32 = 20;
-const HEIGHT: u32 = 20;
-const SNAKE_MOVE_INTERVAL: f64 = 0.15; // ✅ Move every 150ms
+use crate::achievements::Achievements;
+use crate::announce::Announcer;
+use crate::challenge;
+use crate::config::{GameConfig, SnakeConfig};
+use crate::draw::Canvas;
+use crate::env::{Observation, ObservationKind};
+use crate::events::{DeathCause, GameEvent};
+use crate::game::{self, GameKind, RunResult};
+use crate::lockstep::{StateHasher, TICK};
+use crate::replay::Recorder;
+use crate::rng::{self, GameRng};
+use crate::sim::{Command, Sim};
+use crate::theme::Style;
+use crate::view::{self, Hud, View};
 
-#[wasm_bindgen]
-pub struct SnakeGame {
-    last_update_time: f64, // ✅ Track last movement time
-    snake: Vec<(u32, u32)>,
+pub(crate) const GRID_SIZE: f64 = 20.0;
+pub(crate) const WIDTH: u32 = 20;
+pub(crate) const HEIGHT: u32 = 20;
+pub(crate) const SNAKE_MOVE_INTERVAL: f64 = 0.15; // ✅ Move every 150ms
+
+struct Snake {
+    body: Vec<(u32, u32)>,
     direction: (i32, i32),
+    score: u32,
+}
+
+/// ✅ One snake in the middle, or two side by side for two players
+fn starting_snakes(config: &SnakeConfig, players: usize) -> Vec<Snake> {
+    (0..players)
+        .map(|i| Snake {
+            body: vec![(config.width * (i as u32 + 1) / (players as u32 + 1), config.height / 2)],
+            direction: (0, 0),
+            score: 0,
+        })
+        .collect()
+}
+
+/// One frame of Snake: each player's body, head first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnakeView {
+    pub width: u32,
+    pub height: u32,
+    pub snakes: Vec<Vec<(u32, u32)>>,
+    pub food: (u32, u32),
+    pub hud: Hud,
+}
+
+/// The snake rules without a canvas, so they also run natively and in
+/// lockstep with a remote peer. `SnakeGame` draws it and reacts to its events.
+pub struct SnakeSim {
+    last_update_time: f64, // ✅ Track last movement time
+    snakes: Vec<Snake>,
+    previous: Vec<Vec<(u32, u32)>>, // ✅ Bodies before the last move, for `motion`
     food: (u32, u32),
-    context: CanvasRenderingContext2d,
     running: bool,
-    tick_counter: u32,
+    config: SnakeConfig,
+    game_config: GameConfig,
+    rng: GameRng,
+    seed: u64,
+    last_run: Option<(u64, u32)>, // ✅ (seed, score) of the last finished solo run
+    last_result: Option<RunResult>,
+    events: Vec<GameEvent>,
 }
 
-#[wasm_bindgen]
-impl SnakeGame {
-    #[wasm_bindgen(constructor)]
-    pub fn new(canvas: HtmlCanvasElement) -> SnakeGame {
-        let context = canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()
-            .unwrap();
+impl SnakeSim {
+    pub fn new(game_config: &GameConfig) -> SnakeSim {
+        let config = game_config.snake();
+        let seed = rng::run_seed(game_config);
 
-        SnakeGame {
+        let mut sim = SnakeSim {
             last_update_time: 0.0,
-            snake: vec![(10, 10)],
-            direction: (0, 0),
-            food: (5, 5),
-            context,
+            snakes: starting_snakes(&config, game_config.players()),
+            previous: Vec::new(),
+            food: (0, 0), // ✅ Placed from the seed below
             running: false,
-            tick_counter: 0,
-        }
+            config,
+            game_config: game_config.clone(),
+            rng: rng::seeded(seed),
+            seed,
+            last_run: None,
+            last_result: None,
+            events: Vec::new(),
+        };
+        sim.food = sim.food_cell();
+        sim.previous = sim.bodies();
+        sim
     }
 
-    #[wasm_bindgen]
     pub fn update(&mut self, delta_time: f64) {
         if !self.running {
             return;
         }
 
         self.last_update_time += delta_time;
-        if self.last_update_time < SNAKE_MOVE_INTERVAL {
+        if self.last_update_time < self.config.move_interval {
             return; // ✅ Skip updates until enough time has passed
         }
         self.last_update_time = 0.0;
+        self.previous = self.bodies();
+
+        // ✅ Where each snake's head goes; `None` while it hasn't been given a direction
+        let (width, height) = (self.config.width, self.config.height);
+        let mut dead = vec![None; self.snakes.len()];
+        let mut heads = Vec::with_capacity(self.snakes.len());
+        for (i, snake) in self.snakes.iter().enumerate() {
+            let (dx, dy) = snake.direction;
+            if dx == 0 && dy == 0 {
+                heads.push(None);
+                continue;
+            }
+
+            let (head_x, head_y) = snake.body[0];
+            let new_head = if self.config.wrap {
+                // ✅ Come back in on the opposite edge
+                ((head_x as i32 + dx).rem_euclid(width as i32) as u32,
+                 (head_y as i32 + dy).rem_euclid(height as i32) as u32)
+            } else {
+                ((head_x as i32 + dx) as u32, (head_y as i32 + dy) as u32)
+            };
 
-        let (dx, dy) = self.direction;
-        if dx == 0 && dy == 0 {
+            if new_head.0 >= width || new_head.1 >= height {
+                dead[i] = Some(DeathCause::Wall);
+                heads.push(None);
+            } else {
+                heads.push(Some(new_head));
+            }
+        }
+
+        // ✅ Tails move out of the way first, except on a snake that's eating
+        for (snake, head) in self.snakes.iter_mut().zip(&heads) {
+            if matches!(head, Some(head) if *head != self.food) {
+                snake.body.pop();
+            }
+        }
+
+        // ✅ Running into any body, or head-on into the other snake, is fatal
+        for (i, head) in heads.iter().enumerate() {
+            let Some(head) = head else { continue };
+            let hit_body = self.snakes.iter().position(|snake| snake.body.contains(head));
+            let hits_head = heads.iter().enumerate().any(|(j, other)| j != i && other.as_ref() == Some(head));
+            dead[i] = match hit_body {
+                Some(j) if j == i => Some(DeathCause::OwnBody),
+                Some(_) => Some(DeathCause::OtherSnake),
+                None if hits_head => Some(DeathCause::HeadOn),
+                None => None,
+            };
+        }
+
+        if dead.iter().any(Option::is_some) {
+            for (player, cause) in dead.iter().enumerate() {
+                if let Some(cause) = *cause {
+                    self.events.push(GameEvent::Died { player, cause });
+                }
+            }
+            let survivors: Vec<usize> = (0..dead.len()).filter(|&i| dead[i].is_none()).collect();
+            let winner = if survivors.len() == 1 { Some(survivors[0]) } else { None };
+            self.game_over(winner);
             return;
         }
 
-        let (head_x, head_y) = self.snake[0];
-        let new_head = ((head_x as i32 + dx) as u32, (head_y as i32 + dy) as u32);
+        let mut ate = false;
+        for (i, head) in heads.into_iter().enumerate() {
+            let Some(head) = head else { continue };
+            self.snakes[i].body.insert(0, head);
+            if head == self.food {
+                ate = true;
+                self.snakes[i].score += 1;
+                self.events.push(GameEvent::SnakeGrew { length: self.snakes[i].body.len() });
+            }
+        }
+        if ate {
+            self.food = self.food_cell();
+        }
+    }
 
-        if new_head.0 >= WIDTH || new_head.1 >= HEIGHT {
-            self.reset();
+    /// Steers player `player`'s snake (0 or 1).
+    pub fn change_direction(&mut self, player: usize, dx: i32, dy: i32) {
+        let Some(snake) = self.snakes.get_mut(player) else {
             return;
+        };
+        if (dx, dy) != (-snake.direction.0, -snake.direction.1) {
+            snake.direction = (dx, dy);
         }
 
-        if new_head == self.food {
-            self.food = ((js_sys::Math::random() * WIDTH as f64) as u32,
-                        (js_sys::Math::random() * HEIGHT as f64) as u32);
-        } else {
-            self.snake.pop();
+        if !self.running {
+            self.running = true;
+            self.events.push(GameEvent::Started);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.snakes = starting_snakes(&self.config, self.game_config.players());
+        self.seed = rng::run_seed(&self.game_config);
+        self.rng = rng::seeded(self.seed);
+        self.food = self.food_cell();
+        self.previous = self.bodies();
+        self.running = false;
+    }
+
+    /// No run going: before the first input and after a game over.
+    pub fn waiting(&self) -> bool {
+        !self.running
+    }
+
+    /// Every player's score, in player order.
+    pub fn scores(&self) -> Vec<u32> {
+        self.snakes.iter().map(|snake| snake.score).collect()
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    pub fn challenge_token(&self) -> Option<String> {
+        self.last_run
+            .map(|(seed, score)| challenge::encode(&self.game_config, seed, score))
+    }
+
+    /// Events since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Hash of everything that decides the rest of the game, for comparing
+    /// lockstep peers tick by tick.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_f64(self.last_update_time);
+        hasher.write_bool(self.running);
+        hasher.write_u32(self.food.0);
+        hasher.write_u32(self.food.1);
+        for snake in &self.snakes {
+            hasher.write_u32(snake.body.len() as u32);
+            for &(x, y) in &snake.body {
+                hasher.write_u32(x);
+                hasher.write_u32(y);
+            }
+            hasher.write_u32(snake.direction.0 as u32);
+            hasher.write_u32(snake.direction.1 as u32);
+            hasher.write_u32(snake.score);
+        }
+        hasher.write_u64(self.rng.clone().next_u64()); // ✅ Where the food comes from next
+        hasher.finish()
+    }
+
+    pub fn view(&self) -> SnakeView {
+        SnakeView {
+            width: self.config.width,
+            height: self.config.height,
+            snakes: self.snakes.iter().map(|snake| snake.body.clone()).collect(),
+            food: self.food,
+            hud: Hud {
+                scores: self.scores(),
+                result: if self.running { None } else { self.last_result },
+                challenge_score: self.game_config.challenge_score,
+            },
         }
+    }
+
+    /// Player 1's view for `env.rs`. Features: head position and heading,
+    /// the way to the food, which neighbouring cells are deadly, and length.
+    /// Grid: own body, own head, food, other snakes.
+    pub(crate) fn observe(&self, kind: ObservationKind) -> Observation {
+        let (width, height) = (self.config.width, self.config.height);
+        let snake = &self.snakes[0];
+        let (head_x, head_y) = snake.body[0];
+
+        match kind {
+            ObservationKind::Features => {
+                let deadly = |(dx, dy): (i32, i32)| {
+                    let (x, y) = (head_x as i32 + dx, head_y as i32 + dy);
+                    let (x, y) = if self.config.wrap {
+                        (x.rem_euclid(width as i32), y.rem_euclid(height as i32))
+                    } else {
+                        (x, y)
+                    };
+                    let outside = x < 0 || y < 0 || x as u32 >= width || y as u32 >= height;
+                    outside || self.snakes.iter().any(|other| other.body.contains(&(x as u32, y as u32)))
+                };
+
+                let mut features = vec![
+                    head_x as f32 / width as f32,
+                    head_y as f32 / height as f32,
+                    snake.direction.0 as f32,
+                    snake.direction.1 as f32,
+                    (self.food.0 as f32 - head_x as f32) / width as f32,
+                    (self.food.1 as f32 - head_y as f32) / height as f32,
+                ];
+                features.extend([(0, -1), (0, 1), (-1, 0), (1, 0)].map(|step| deadly(step) as u8 as f32));
+                features.push(snake.body.len() as f32 / (width * height) as f32);
+                Observation::features(features)
+            }
+            ObservationKind::Grid => {
+                let mut grid = Observation::grid(4, width as usize, height as usize);
+                for &(x, y) in &snake.body {
+                    grid.set(0, x as usize, y as usize);
+                }
+                grid.set(1, head_x as usize, head_y as usize);
+                grid.set(2, self.food.0 as usize, self.food.1 as usize);
+                for other in &self.snakes[1..] {
+                    for &(x, y) in &other.body {
+                        grid.set(3, x as usize, y as usize);
+                    }
+                }
+                grid
+            }
+        }
+    }
+
+    /// Where each snake was before its last move, and how far a frame drawn
+    /// `alpha` of a tick after the last update is into the next one.
+    pub(crate) fn motion(&self, alpha: f64) -> SnakeMotion {
+        let progress = (self.last_update_time + alpha * TICK) / self.config.move_interval;
+        SnakeMotion { previous: self.previous.clone(), progress: progress.min(1.0) }
+    }
+
+    fn bodies(&self) -> Vec<Vec<(u32, u32)>> {
+        self.snakes.iter().map(|snake| snake.body.clone()).collect()
+    }
 
-        if self.snake.contains(&new_head) {
-            self.reset();
+    fn game_over(&mut self, winner: Option<usize>) {
+        let score = self.snakes[0].score;
+        if self.snakes.len() == 1 {
+            self.last_run = Some((self.seed, score));
+            self.last_result = Some(RunResult::Score(score));
         } else {
-            self.snake.insert(0, new_head);
+            self.last_result = Some(RunResult::Winner(winner));
         }
+        self.events.push(GameEvent::GameOver { score });
+        self.reset();
+    }
+
+    /// ✅ A random cell clear of every snake; where the food was if they fill the board
+    fn food_cell(&mut self) -> (u32, u32) {
+        let cells = (self.config.width * self.config.height) as usize;
+        if self.snakes.iter().map(|snake| snake.body.len()).sum::<usize>() >= cells {
+            return self.food;
+        }
+        loop {
+            let cell = self.random_cell();
+            if !self.snakes.iter().any(|snake| snake.body.contains(&cell)) {
+                return cell;
+            }
+        }
+    }
+
+    fn random_cell(&mut self) -> (u32, u32) {
+        (self.rng.gen_range(0..self.config.width),
+         self.rng.gen_range(0..self.config.height))
+    }
+}
+
+/// What `draw` needs to slide the snakes from cell to cell between moves.
+pub(crate) struct SnakeMotion {
+    previous: Vec<Vec<(u32, u32)>>,
+    progress: f64,
+}
+
+/// Draws `view`, with the snakes `motion.progress` of the way from their
+/// previous cells when there's a `motion`.
+pub(crate) fn draw(context: &dyn Canvas, view: &SnakeView, motion: Option<&SnakeMotion>, style: &Style) {
+    let palette = style.palette();
+    let (width, height) = ((view.width as f64) * GRID_SIZE, (view.height as f64) * GRID_SIZE);
+    context.set_fill_style_str(palette.background);
+    context.fill_rect(0.0, 0.0, width, height);
+
+    for (i, (body, color)) in view.snakes.iter().zip(palette.snakes).enumerate() {
+        context.set_fill_style_str(color);
+        for (j, &(x, y)) in body.iter().enumerate() {
+            let (x, y) = (x as f64, y as f64);
+            // ✅ Segment `j` slides from where segment `j` was; a new tail segment stays put
+            let (x, y) = match motion.and_then(|motion| Some((motion.previous.get(i)?.get(j)?, motion.progress))) {
+                Some((&(from_x, from_y), progress)) => view::lerp_cell((from_x as f64, from_y as f64), (x, y), progress),
+                None => (x, y),
+            };
+            context.fill_rect(x * GRID_SIZE, y * GRID_SIZE, GRID_SIZE, GRID_SIZE);
+            if j == 0 && style.cues && view.snakes.len() > 1 {
+                let center = ((x + 0.5) * GRID_SIZE, (y + 0.5) * GRID_SIZE);
+                game::draw_cue(context, &(i + 1).to_string(), center, GRID_SIZE * 0.6, palette.background);
+                context.set_fill_style_str(color);
+            }
+        }
+    }
+
+    context.set_fill_style_str(palette.food);
+    let (fx, fy) = view.food;
+    context.fill_rect((fx as f64) * GRID_SIZE, (fy as f64) * GRID_SIZE, GRID_SIZE, GRID_SIZE);
+    if style.cues {
+        // ✅ A ring, so the food stands out from the snakes by shape too
+        context.set_stroke_style_str(palette.text);
+        context.set_line_width(2.0);
+        context.begin_path();
+        context
+            .arc(
+                (fx as f64 + 0.5) * GRID_SIZE,
+                (fy as f64 + 0.5) * GRID_SIZE,
+                GRID_SIZE * 0.75,
+                0.0,
+                f64::consts::PI * 2.0,
+            )
+            .unwrap();
+        context.stroke();
+    }
+
+    game::draw_hud(context, width, height, &view.hud, style);
+}
+
+#[wasm_bindgen]
+pub struct SnakeGame {
+    sim: SnakeSim,
+    context: CanvasRenderingContext2d,
+    achievements: Achievements,
+    announcer: Announcer,
+    recorder: Recorder,
+    style: Style,
+}
+
+#[wasm_bindgen]
+impl SnakeGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas: HtmlCanvasElement) -> SnakeGame {
+        Self::with_config(canvas, &GameConfig::new(GameKind::Snake))
     }
 
     #[wasm_bindgen]
-    pub fn render(&self) {
-        self.context.set_fill_style(&"black".into());
-        self.context.fill_rect(0.0, 0.0, (WIDTH as f64) * GRID_SIZE, (HEIGHT as f64) * GRID_SIZE);
+    pub fn with_config(canvas: HtmlCanvasElement, game_config: &GameConfig) -> SnakeGame {
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
 
-        self.context.set_fill_style(&"green".into());
-        for (x, y) in &self.snake {
-            self.context.fill_rect((*x as f64) * GRID_SIZE, (*y as f64) * GRID_SIZE, GRID_SIZE, GRID_SIZE);
+        SnakeGame {
+            sim: SnakeSim::new(game_config),
+            context,
+            achievements: Achievements::load(),
+            announcer: Announcer::new(GameKind::Snake),
+            recorder: Recorder::new(),
+            style: game_config.style(),
         }
+    }
+
+    #[wasm_bindgen]
+    pub fn update(&mut self, delta_time: f64) {
+        self.achievements.update(delta_time);
+        self.sim.update(delta_time);
+        self.record_events();
+        self.recorder.tick();
+    }
+
+    #[wasm_bindgen]
+    pub fn render(&self) {
+        self.draw_frame(None);
+    }
 
-        self.context.set_fill_style(&"red".into());
-        let (fx, fy) = self.food;
-        self.context.fill_rect((fx as f64) * GRID_SIZE, (fy as f64) * GRID_SIZE, GRID_SIZE, GRID_SIZE);
+    /// Draws the snakes `alpha` (0 to 1) of a tick past the last update, so
+    /// they glide between cells on displays faster than the tick rate.
+    #[wasm_bindgen]
+    pub fn render_at(&self, alpha: f64) {
+        self.draw_frame(Some(&self.sim.motion(alpha)));
     }
 
     #[wasm_bindgen]
     pub fn change_direction(&mut self, dx: i32, dy: i32) {
-        if (dx, dy) != (-self.direction.0, -self.direction.1) {
-            self.direction = (dx, dy);
-        }
+        self.change_player_direction(0, dx, dy);
+    }
 
-        if !self.running {
-            self.running = true;
-        }
+    /// Steers player `player`'s snake (0 or 1).
+    #[wasm_bindgen]
+    pub fn change_player_direction(&mut self, player: usize, dx: i32, dy: i32) {
+        self.command(Command::Steer(player, dx, dy));
     }
 
     #[wasm_bindgen]
     pub fn reset(&mut self) {
-        self.snake = vec![(10, 10)];
-        self.direction = (0, 0);
-        self.food = ((js_sys::Math::random() * WIDTH as f64) as u32,
-                     (js_sys::Math::random() * HEIGHT as f64) as u32);
-        self.running = false;
+        self.sim.reset();
+        self.recorder.cancel();
+    }
+
+    /// Player 1's score.
+    #[wasm_bindgen]
+    pub fn score(&self) -> u32 {
+        self.sim.scores()[0]
+    }
+
+    /// Every player's score, in player order.
+    #[wasm_bindgen]
+    pub fn scores(&self) -> Vec<u32> {
+        self.sim.scores()
+    }
+
+    /// Token for a `?challenge=` link replaying the last finished run.
+    #[wasm_bindgen]
+    pub fn challenge_token(&self) -> Option<String> {
+        self.sim.challenge_token()
+    }
+
+    /// JSON input replay of the last finished solo run, for the leaderboard.
+    #[wasm_bindgen]
+    pub fn replay(&self) -> Option<String> {
+        self.recorder.last().map(|replay| serde_json::to_string(replay).expect("replays serialize"))
+    }
+
+    /// Solo runs finished so far; when it changes there is a new challenge
+    /// token and replay to offer.
+    #[wasm_bindgen]
+    pub fn finished_runs(&self) -> u32 {
+        self.recorder.finished()
+    }
+}
+
+impl SnakeGame {
+    pub(crate) fn sim(&self) -> &SnakeSim {
+        &self.sim
+    }
+
+    pub(crate) fn view(&self) -> View {
+        View::Snake(self.sim.view())
+    }
+
+    pub(crate) fn waiting(&self) -> bool {
+        self.sim.waiting()
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Whether a bot is playing player 1, whose runs unlock no achievements.
+    pub(crate) fn set_bot(&mut self, bot: bool) {
+        self.achievements.set_bot(bot);
+    }
+
+    fn draw_frame(&self, motion: Option<&SnakeMotion>) {
+        let view = self.sim.view();
+        draw(&self.context, &view, motion, &self.style);
+        let (width, height) = ((view.width as f64) * GRID_SIZE, (view.height as f64) * GRID_SIZE);
+        self.achievements.render(&self.context, width, height, &self.style);
+    }
+
+    fn record_events(&mut self) {
+        let events = self.sim.take_events();
+        self.recorder.events(&events, &self.sim);
+        for event in &events {
+            self.achievements.record(event);
+        }
+        self.announcer.update(&events, &self.sim, self.style.strings());
+    }
+
+    pub(crate) fn command(&mut self, command: Command) {
+        Sim::command(&mut self.sim, command);
+        self.record_events();
+        self.recorder.command(command);
     }
 }
@@ -0,0 +1,181 @@
+//! Spectating: a running game publishes its `View` as a keyframe followed by
+//! small deltas, and any number of viewers rebuild the same frames from them.
+//!
+//! Deltas are JSON merge patches (RFC 7386) with one addition: a patch object
+//! applied to an array updates elements by index, so eating one pellet doesn't
+//! resend the whole maze. Transport is up to the caller (`broadcast.rs`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::view::View;
+
+/// A fresh keyframe at least this often, so a dropped delta heals by itself.
+const KEYFRAME_INTERVAL: u32 = 120;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpectateMessage {
+    Keyframe { seq: u32, view: View },
+    Delta { seq: u32, patch: Value },
+    /// A viewer joined or lost track; the publisher answers with a keyframe.
+    Hello,
+}
+
+/// Turns a game's frames into messages for its spectators.
+#[derive(Default)]
+pub struct Publisher {
+    last: Option<Value>,
+    seq: u32,
+    since_keyframe: u32,
+}
+
+impl Publisher {
+    pub fn new() -> Publisher {
+        Publisher::default()
+    }
+
+    /// Makes the next frame a keyframe.
+    pub fn request_keyframe(&mut self) {
+        self.last = None;
+    }
+
+    /// The message bringing viewers up to `view`; `None` if nothing changed.
+    pub fn publish(&mut self, view: &View) -> Option<SpectateMessage> {
+        let value = serde_json::to_value(view).expect("views serialize");
+
+        let message = match &self.last {
+            Some(last) if self.since_keyframe < KEYFRAME_INTERVAL => {
+                let patch = diff(last, &value)?;
+                self.since_keyframe += 1;
+                SpectateMessage::Delta { seq: self.seq + 1, patch }
+            }
+            _ => {
+                self.since_keyframe = 0;
+                SpectateMessage::Keyframe { seq: self.seq + 1, view: view.clone() }
+            }
+        };
+
+        self.seq += 1;
+        self.last = Some(value);
+        Some(message)
+    }
+}
+
+/// Rebuilds the publisher's frames from its messages.
+#[derive(Default)]
+pub struct Viewer {
+    seq: Option<u32>,
+    state: Option<Value>,
+    view: Option<View>,
+}
+
+impl Viewer {
+    pub fn new() -> Viewer {
+        Viewer::default()
+    }
+
+    /// The latest frame, once a keyframe has arrived.
+    pub fn view(&self) -> Option<&View> {
+        self.view.as_ref()
+    }
+
+    /// Whether the viewer has a frame and has seen every delta since; if not,
+    /// it should say `Hello` to get a keyframe.
+    pub fn in_sync(&self) -> bool {
+        self.seq.is_some()
+    }
+
+    pub fn receive(&mut self, message: SpectateMessage) {
+        match message {
+            SpectateMessage::Keyframe { seq, view } => {
+                self.state = serde_json::to_value(&view).ok();
+                self.view = Some(view);
+                self.seq = Some(seq);
+            }
+            SpectateMessage::Delta { seq, patch } => {
+                let (Some(last), Some(state)) = (self.seq, &mut self.state) else { return };
+                if seq != last + 1 {
+                    self.seq = None; // ✅ Missed one; keep showing the last frame until a keyframe
+                    return;
+                }
+
+                apply(state, &patch);
+                match serde_json::from_value(state.clone()) {
+                    Ok(view) => {
+                        self.view = Some(view);
+                        self.seq = Some(seq);
+                    }
+                    Err(_) => self.seq = None,
+                }
+            }
+            SpectateMessage::Hello => {}
+        }
+    }
+}
+
+/// The patch taking `old` to `new`, or `None` if they are equal.
+fn diff(old: &Value, new: &Value) -> Option<Value> {
+    if old == new {
+        return None;
+    }
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut patch = Map::new();
+            for (key, value) in new {
+                let changed = match old.get(key) {
+                    Some(old_value) => diff(old_value, value),
+                    None => Some(value.clone()),
+                };
+                if let Some(changed) = changed {
+                    patch.insert(key.clone(), changed);
+                }
+            }
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                patch.insert(key.clone(), Value::Null);
+            }
+            Some(Value::Object(patch))
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            let mut patch = Map::new();
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                if let Some(changed) = diff(old, new) {
+                    patch.insert(i.to_string(), changed);
+                }
+            }
+            // ✅ Shifting arrays (a moving snake) change every element; send those whole
+            let (patch, whole) = (Value::Object(patch), Value::Array(new.clone()));
+            if patch.to_string().len() < whole.to_string().len() {
+                Some(patch)
+            } else {
+                Some(whole)
+            }
+        }
+        _ => Some(new.clone()),
+    }
+}
+
+fn apply(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Array(items), Value::Object(changes)) => {
+            for (index, change) in changes {
+                if let Some(item) = index.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                    apply(item, change);
+                }
+            }
+        }
+        (Value::Object(fields), Value::Object(changes)) => {
+            for (key, change) in changes {
+                if change.is_null() {
+                    fields.remove(key);
+                } else if let Some(field) = fields.get_mut(key) {
+                    apply(field, change);
+                } else {
+                    fields.insert(key.clone(), change.clone());
+                }
+            }
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
@@ -0,0 +1,215 @@
+//! The games in a terminal (`src/bin/wasm_games-tui.rs`): a `View` drawn as
+//! coloured text, and key presses turned into commands. Grid games take two
+//! columns per tile so they come out roughly square; Flappy Bird is sampled
+//! at 10×20 pixels per character.
+
+use std::fmt::Write;
+
+use crossterm::event::KeyCode;
+use crossterm::style::{Color, ResetColor, SetForegroundColor};
+
+use crate::flappy::FlappyView;
+use crate::game::{self, GameKind};
+use crate::input::{self, Action};
+use crate::locale::{fill, Strings};
+use crate::pacman::PacmanView;
+use crate::sim::Command;
+use crate::snake::SnakeView;
+use crate::theme::Style;
+use crate::view::{Hud, View};
+use crate::{flappy, pacman};
+
+const FLAPPY_COLUMN: f64 = 10.0; // ✅ Canvas pixels per character
+const FLAPPY_ROW: f64 = 20.0;
+const FLAPPY_BIRD_X: f64 = 50.0;
+const BIRD_COLORS: [Color; 2] = [Color::Yellow, Color::Cyan];
+const SNAKE_COLORS: [Color; 2] = [Color::Green, Color::Blue];
+const GHOST_COLORS: [Color; 4] = [Color::Red, Color::Magenta, Color::Cyan, Color::DarkYellow];
+
+/// Characters with their colours, one row per terminal line.
+struct Screen {
+    cells: Vec<Vec<(char, Color)>>,
+}
+
+impl Screen {
+    fn new(width: usize, height: usize) -> Screen {
+        Screen { cells: vec![vec![(' ', Color::Reset); width]; height] }
+    }
+
+    fn set(&mut self, x: usize, y: usize, text: &str, color: Color) {
+        let Some(row) = self.cells.get_mut(y) else { return };
+        for (i, ch) in text.chars().enumerate() {
+            if let Some(cell) = row.get_mut(x + i) {
+                *cell = (ch, color);
+            }
+        }
+    }
+
+    /// Two columns for tile `(x, y)`, inside the one-character border.
+    fn tile(&mut self, (x, y): (usize, usize), text: &str, color: Color) {
+        self.set(1 + x * 2, 1 + y, text, color);
+    }
+
+    fn border(&mut self) {
+        let (width, height) = (self.cells[0].len(), self.cells.len());
+        for x in 0..width {
+            self.set(x, 0, "─", Color::DarkGrey);
+            self.set(x, height - 1, "─", Color::DarkGrey);
+        }
+        for y in 0..height {
+            self.set(0, y, "│", Color::DarkGrey);
+            self.set(width - 1, y, "│", Color::DarkGrey);
+        }
+        self.set(0, 0, "┌", Color::DarkGrey);
+        self.set(width - 1, 0, "┐", Color::DarkGrey);
+        self.set(0, height - 1, "└", Color::DarkGrey);
+        self.set(width - 1, height - 1, "┘", Color::DarkGrey);
+    }
+
+    /// Puts the end-of-run banner over the middle of the screen.
+    fn banner(&mut self, hud: &Hud, strings: &Strings) {
+        let Some((title, line)) = game::result_banner(hud, strings) else { return };
+        let (width, middle) = (self.cells[0].len(), self.cells.len() / 2);
+        for (y, text) in [(middle - 1, title), (middle, line)] {
+            let text = format!(" {} ", text);
+            let x = width.saturating_sub(text.chars().count()) / 2;
+            self.set(x, y, &text, Color::White);
+        }
+    }
+}
+
+/// The frame as text, lines ending in `\r\n` (for raw mode), starting with
+/// the score line, with text in `style`'s language.
+pub fn render(view: &View, style: &Style) -> String {
+    let (mut screen, hud) = match view {
+        View::Flappy(view) => (flappy_screen(view), &view.hud),
+        View::Snake(view) => (snake_screen(view), &view.hud),
+        View::Pacman(view) => (pacman_screen(view), &view.hud),
+    };
+
+    let strings = style.strings();
+    screen.banner(hud, strings);
+    let mut out = String::new();
+    let scores: Vec<String> = match hud.scores[..] {
+        [score] => vec![fill(strings.score, &[&score])],
+        _ => hud.scores.iter().enumerate().map(|(i, score)| fill(strings.player_score, &[&(i + 1), score])).collect(),
+    };
+    out.push_str(&scores.join("   "));
+    if let Some(target) = hud.challenge_score {
+        write!(out, "   {}", fill(strings.beat, &[&target])).unwrap();
+    }
+    out.push_str("\x1b[K\r\n"); // ✅ Clear what's left of a longer previous line
+
+    let mut color = Color::Reset;
+    for row in &screen.cells {
+        for &(ch, cell_color) in row {
+            if cell_color != color {
+                write!(out, "{}", SetForegroundColor(cell_color)).unwrap();
+                color = cell_color;
+            }
+            out.push(ch);
+        }
+        out.push_str("\r\n");
+    }
+    write!(out, "{}", ResetColor).unwrap();
+    out
+}
+
+fn flappy_screen(view: &FlappyView) -> Screen {
+    let (width, height) = ((flappy::WIDTH / FLAPPY_COLUMN) as usize, (flappy::HEIGHT / FLAPPY_ROW) as usize);
+    let mut screen = Screen::new(width + 2, height + 2);
+    screen.border();
+
+    for &(pipe_x, gap_y) in &view.pipes {
+        let first = (pipe_x / FLAPPY_COLUMN).round().max(0.0) as usize;
+        let last = ((pipe_x + flappy::PIPE_WIDTH) / FLAPPY_COLUMN).round().min(width as f64) as usize;
+        for y in 0..height {
+            let center = (y as f64 + 0.5) * FLAPPY_ROW;
+            if center < gap_y || center > gap_y + view.pipe_gap {
+                for x in first..last {
+                    screen.set(1 + x, 1 + y, "█", Color::DarkGreen);
+                }
+            }
+        }
+    }
+
+    let x = (FLAPPY_BIRD_X / FLAPPY_COLUMN) as usize;
+    for (bird, color) in view.birds.iter().zip(BIRD_COLORS) {
+        if let Some(y) = *bird {
+            let y = ((y / FLAPPY_ROW) as usize).min(height - 1);
+            screen.set(x, 1 + y, "(o>", color);
+        }
+    }
+
+    screen
+}
+
+fn snake_screen(view: &SnakeView) -> Screen {
+    let mut screen = Screen::new(view.width as usize * 2 + 2, view.height as usize + 2);
+    screen.border();
+
+    let (fx, fy) = view.food;
+    screen.tile((fx as usize, fy as usize), "██", Color::Red);
+    for (body, color) in view.snakes.iter().zip(SNAKE_COLORS) {
+        for &(x, y) in body {
+            screen.tile((x as usize, y as usize), "██", color);
+        }
+    }
+
+    screen
+}
+
+fn pacman_screen(view: &PacmanView) -> Screen {
+    let mut screen = Screen::new(pacman::WIDTH * 2 + 2, pacman::HEIGHT + 2);
+    screen.border();
+
+    for (y, row) in view.rows.iter().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            match cell {
+                '#' => screen.tile((x, y), "██", Color::Blue),
+                '.' => screen.tile((x, y), "· ", Color::White),
+                'o' => screen.tile((x, y), "● ", Color::White),
+                _ => {}
+            }
+        }
+    }
+
+    screen.tile(view.pacman, "C ", Color::Yellow);
+    for (i, &ghost) in view.ghosts.iter().enumerate() {
+        let color = if view.frightened { Color::DarkBlue } else { GHOST_COLORS[i % GHOST_COLORS.len()] };
+        let text = if view.controlled_ghost == Some(i) { "M*" } else { "M " }; // ✅ Player 2's ghost
+        screen.tile(ghost, text, color);
+    }
+
+    screen
+}
+
+/// The command for a terminal key press: arrows (Space flaps) for player 1,
+/// WASD for player 2, as in the browser. Terminals don't report key releases,
+/// so Pac-Man keeps going the last way pressed; Space stops him.
+pub fn command(game: GameKind, players: usize, key: KeyCode) -> Option<Command> {
+    let (key, code) = match key {
+        KeyCode::Up => ("ArrowUp", ""),
+        KeyCode::Down => ("ArrowDown", ""),
+        KeyCode::Left => ("ArrowLeft", ""),
+        KeyCode::Right => ("ArrowRight", ""),
+        KeyCode::Char(' ') => (" ", "Space"),
+        KeyCode::Char(ch) => match ch.to_ascii_lowercase() {
+            'w' => ("w", "KeyW"),
+            'a' => ("a", "KeyA"),
+            's' => ("s", "KeyS"),
+            'd' => ("d", "KeyD"),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let (player, action) = input::key_binding(key, code).filter(|&(player, _)| player < players)?;
+
+    match (game, action.direction()) {
+        (GameKind::Flappy, _) if matches!(action, Action::Flap | Action::Up) => Some(Command::Flap(player)),
+        (GameKind::Flappy, _) => None,
+        (GameKind::Pacman, None) => Some(Command::Steer(player, 0, 0)),
+        (_, Some((dx, dy))) => Some(Command::Steer(player, dx, dy)),
+        (GameKind::Snake, None) => None,
+    }
+}
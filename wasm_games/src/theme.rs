@@ -0,0 +1,280 @@
+//! Colour themes. Every draw function takes its colours from a `Palette`
+//! rather than literals, so `?theme=neon` (or a `theme` attribute, or
+//! `Game::set_theme` from a settings menu) recolours the games, their HUD and
+//! overlays, and the page around the canvas in one go.
+//!
+//! For accessibility there are colour-blind-safe and high-contrast themes,
+//! and `?cues=on` adds shapes for what is otherwise told apart by colour
+//! alone: letters on the ghosts, player numbers on snakes and birds, and a
+//! ring around the snake's food. `?motion=reduced` (the default under
+//! `prefers-reduced-motion`) stops the title screen flashing and the attract
+//! demo playing by itself.
+
+use serde::Serialize;
+
+use crate::locale::{Language, Strings};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// The original colours.
+    #[default]
+    Classic,
+    Neon,
+    /// Four shades of green, like the original Game Boy screen.
+    GameBoy,
+    Light,
+    /// The grey of the rest of the site.
+    Site,
+    /// Okabe-Ito colours, safe for red-green (deuteranopia) colour blindness.
+    Deuteranopia,
+    /// Okabe-Ito colours without the reds that look dark with protanopia.
+    Protanopia,
+    /// Saturated colours on black, for low vision.
+    Contrast,
+}
+
+/// How frames are drawn: the theme's colours, plus shape cues if asked for,
+/// without flashing or motion nobody asked for under `reduced_motion`, and
+/// with text in `language`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub theme: Theme,
+    pub cues: bool,
+    pub reduced_motion: bool,
+    pub language: Language,
+}
+
+impl Style {
+    pub fn palette(&self) -> &'static Palette {
+        self.theme.palette()
+    }
+
+    pub fn strings(&self) -> &'static Strings {
+        self.language.strings()
+    }
+}
+
+/// Every colour a frame is drawn with, as canvas colour strings.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Palette {
+    pub background: &'static str,
+    /// HUD, banners and status messages.
+    pub text: &'static str,
+    /// Behind achievement toasts.
+    pub panel: &'static str,
+    /// Achievement toast text.
+    pub highlight: &'static str,
+    /// Flappy Bird, player 1 then player 2.
+    pub birds: [&'static str; 2],
+    pub pipes: &'static str,
+    /// Snakes, player 1 then player 2.
+    pub snakes: [&'static str; 2],
+    pub food: &'static str,
+    pub walls: &'static str,
+    pub pellets: &'static str,
+    pub pacman: &'static str,
+    /// Blinky, Pinky, Inky, Clyde.
+    pub ghosts: [&'static str; 4],
+    pub frightened: &'static str,
+    /// Page background and canvas border around the game.
+    pub page: &'static str,
+    pub border: &'static str,
+}
+
+const CLASSIC: Palette = Palette {
+    background: "black",
+    text: "white",
+    panel: "rgba(0, 0, 0, 0.75)",
+    highlight: "gold",
+    birds: ["yellow", "#00BFFF"],
+    pipes: "green",
+    snakes: ["green", "#1E90FF"],
+    food: "red",
+    walls: "blue",
+    pellets: "white",
+    pacman: "yellow",
+    ghosts: ["#FF0000", "#FFC0CB", "#00FFFF", "#FFA500"],
+    frightened: "#2121DE",
+    page: "#404040",
+    border: "#a0a0a0",
+};
+
+const NEON: Palette = Palette {
+    background: "#0a0014",
+    text: "#f0f0ff",
+    panel: "rgba(10, 0, 20, 0.8)",
+    highlight: "#ffe600",
+    birds: ["#ffe600", "#00f0ff"],
+    pipes: "#39ff14",
+    snakes: ["#39ff14", "#00f0ff"],
+    food: "#ff2079",
+    walls: "#b026ff",
+    pellets: "#f0f0ff",
+    pacman: "#ffe600",
+    ghosts: ["#ff2079", "#ff8ad8", "#00f0ff", "#ff9e00"],
+    frightened: "#3d5afe",
+    page: "#0a0014",
+    border: "#b026ff",
+};
+
+const GAME_BOY: Palette = Palette {
+    background: "#9bbc0f",
+    text: "#0f380f",
+    panel: "rgba(139, 172, 15, 0.9)",
+    highlight: "#0f380f",
+    birds: ["#0f380f", "#306230"],
+    pipes: "#306230",
+    snakes: ["#0f380f", "#8bac0f"],
+    food: "#306230",
+    walls: "#306230",
+    pellets: "#0f380f",
+    pacman: "#0f380f",
+    ghosts: ["#306230", "#0f380f", "#306230", "#0f380f"],
+    frightened: "#8bac0f",
+    page: "#0f380f",
+    border: "#8bac0f",
+};
+
+const LIGHT: Palette = Palette {
+    background: "#f4f4f4",
+    text: "#202020",
+    panel: "rgba(255, 255, 255, 0.85)",
+    highlight: "#b8860b",
+    birds: ["#e6a800", "#0077cc"],
+    pipes: "#2e8b57",
+    snakes: ["#2e8b57", "#0077cc"],
+    food: "#d32f2f",
+    walls: "#3f51b5",
+    pellets: "#606060",
+    pacman: "#e6a800",
+    ghosts: ["#d32f2f", "#e91e63", "#0097a7", "#ef6c00"],
+    frightened: "#7986cb",
+    page: "#e0e0e0",
+    border: "#a0a0a0",
+};
+
+const SITE: Palette = Palette {
+    background: "#404040",
+    text: "#f0f0f0",
+    panel: "rgba(32, 32, 32, 0.85)",
+    highlight: "#1AAF5C",
+    birds: ["#ffd54f", "#4fc3f7"],
+    pipes: "#1AAF5C",
+    snakes: ["#1AAF5C", "#4fc3f7"],
+    food: "#ff6e6e",
+    walls: "#a0a0a0",
+    pellets: "#f0f0f0",
+    pacman: "#ffd54f",
+    ghosts: ["#ff6e6e", "#f8a5c2", "#4fc3f7", "#ffb74d"],
+    frightened: "#5c6bc0",
+    page: "#404040",
+    border: "#a0a0a0",
+};
+
+const DEUTERANOPIA: Palette = Palette {
+    background: "#000000",
+    text: "#ffffff",
+    panel: "rgba(0, 0, 0, 0.8)",
+    highlight: "#F0E442",
+    birds: ["#F0E442", "#56B4E9"],
+    pipes: "#009E73",
+    snakes: ["#56B4E9", "#CC79A7"],
+    food: "#F0E442",
+    walls: "#0072B2",
+    pellets: "#ffffff",
+    pacman: "#F0E442",
+    ghosts: ["#D55E00", "#CC79A7", "#56B4E9", "#E69F00"],
+    frightened: "#0072B2",
+    page: "#202020",
+    border: "#a0a0a0",
+};
+
+const PROTANOPIA: Palette = Palette {
+    background: "#000000",
+    text: "#ffffff",
+    panel: "rgba(0, 0, 0, 0.8)",
+    highlight: "#F0E442",
+    birds: ["#F0E442", "#56B4E9"],
+    pipes: "#009E73",
+    snakes: ["#56B4E9", "#CC79A7"],
+    food: "#E69F00",
+    walls: "#0072B2",
+    pellets: "#ffffff",
+    pacman: "#F0E442",
+    ghosts: ["#E69F00", "#CC79A7", "#56B4E9", "#009E73"],
+    frightened: "#0072B2",
+    page: "#202020",
+    border: "#a0a0a0",
+};
+
+const CONTRAST: Palette = Palette {
+    background: "#000000",
+    text: "#ffffff",
+    panel: "#000000",
+    highlight: "#ffff00",
+    birds: ["#ffff00", "#00ffff"],
+    pipes: "#00ff00",
+    snakes: ["#00ff00", "#00ffff"],
+    food: "#ff00ff",
+    walls: "#ffffff",
+    pellets: "#ffff00",
+    pacman: "#ffff00",
+    ghosts: ["#ff4040", "#ff80ff", "#00ffff", "#ffa000"],
+    frightened: "#4040ff",
+    page: "#000000",
+    border: "#ffffff",
+};
+
+impl Theme {
+    pub const ALL: [Theme; 8] = [
+        Theme::Classic,
+        Theme::Neon,
+        Theme::GameBoy,
+        Theme::Light,
+        Theme::Site,
+        Theme::Deuteranopia,
+        Theme::Protanopia,
+        Theme::Contrast,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Classic => "classic",
+            Theme::Neon => "neon",
+            Theme::GameBoy => "gameboy",
+            Theme::Light => "light",
+            Theme::Site => "site",
+            Theme::Deuteranopia => "deuteranopia",
+            Theme::Protanopia => "protanopia",
+            Theme::Contrast => "contrast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "classic" => Some(Theme::Classic),
+            "neon" => Some(Theme::Neon),
+            "gameboy" => Some(Theme::GameBoy),
+            "light" => Some(Theme::Light),
+            "site" => Some(Theme::Site),
+            "deuteranopia" => Some(Theme::Deuteranopia),
+            "protanopia" => Some(Theme::Protanopia),
+            "contrast" => Some(Theme::Contrast),
+            _ => None,
+        }
+    }
+
+    pub fn palette(self) -> &'static Palette {
+        match self {
+            Theme::Classic => &CLASSIC,
+            Theme::Neon => &NEON,
+            Theme::GameBoy => &GAME_BOY,
+            Theme::Light => &LIGHT,
+            Theme::Site => &SITE,
+            Theme::Deuteranopia => &DEUTERANOPIA,
+            Theme::Protanopia => &PROTANOPIA,
+            Theme::Contrast => &CONTRAST,
+        }
+    }
+}
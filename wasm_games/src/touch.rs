@@ -0,0 +1,239 @@
+//! Touch controls for phones, from pointer events in canvas pixels. In
+//! Flappy Bird every tap flaps. In the grid games a finger moving
+//! `swipe_threshold` pixels steers that way, and keeps steering that way
+//! until it lifts or swipes again, like holding an arrow key; Pac-Man stops
+//! when it lifts. With `dpad` on, they also get an on-screen D-pad in the
+//! bottom right corner, held like the arrow keys.
+
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use crate::config::TouchConfig;
+use crate::input::Action;
+use crate::theme::Style;
+
+/// The D-pad's side as a fraction of the canvas's shorter side.
+const DPAD_SIZE: f64 = 0.36;
+const DPAD_MARGIN: f64 = 8.0;
+/// Cells of the D-pad's 3×3 grid that are buttons.
+const DPAD_BUTTONS: [(Action, (f64, f64)); 4] = [
+    (Action::Up, (1.0, 0.0)),
+    (Action::Left, (0.0, 1.0)),
+    (Action::Right, (2.0, 1.0)),
+    (Action::Down, (1.0, 2.0)),
+];
+
+pub(crate) struct Touch {
+    gestures: Gestures,
+    context: CanvasRenderingContext2d,
+}
+
+/// What the fingers do, apart from drawing the D-pad.
+struct Gestures {
+    config: TouchConfig,
+    /// The canvas's size in pixels.
+    size: (f64, f64),
+    /// Every tap is a flap, as in Flappy Bird, rather than a swipe.
+    taps: bool,
+    /// The finger being followed, if one is down: where the current swipe
+    /// started, and the direction it's holding.
+    finger: Option<Finger>,
+}
+
+struct Finger {
+    id: i32,
+    origin: (f64, f64),
+    held: Option<Action>,
+    /// Pressed on the D-pad rather than swiping.
+    on_dpad: bool,
+}
+
+impl Touch {
+    /// `taps` for Flappy Bird, where a tap anywhere flaps.
+    pub(crate) fn new(config: TouchConfig, taps: bool, canvas: &HtmlCanvasElement) -> Touch {
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+        let size = (canvas.width() as f64, canvas.height() as f64);
+        Touch { gestures: Gestures { config, size, taps, finger: None }, context }
+    }
+
+    pub(crate) fn set_dpad(&mut self, dpad: bool) {
+        self.gestures.config.dpad = dpad;
+    }
+
+    /// A finger (or the mouse) goes down at `(x, y)`; returns the direction
+    /// it presses if it's on the D-pad, or `Flap` for a tap in Flappy Bird.
+    /// Other fingers while one is down are ignored.
+    pub(crate) fn down(&mut self, id: i32, point: (f64, f64)) -> Option<Action> {
+        self.gestures.down(id, point)
+    }
+
+    /// The finger moves to `(x, y)`. Returns the direction it now holds and
+    /// the one it let go of, if it swiped a new way (or slid to another
+    /// D-pad button).
+    pub(crate) fn moved(&mut self, id: i32, point: (f64, f64)) -> Option<(Action, Option<Action>)> {
+        self.gestures.moved(id, point)
+    }
+
+    /// The finger lifts (or the browser cancels it); returns the direction
+    /// it let go of.
+    pub(crate) fn up(&mut self, id: i32) -> Option<Action> {
+        self.gestures.up(id)
+    }
+
+    /// Draws the D-pad over the frame, if it's on, with the held button lit.
+    pub(crate) fn render(&self, style: &Style) {
+        if !self.gestures.config.dpad {
+            return;
+        }
+        let palette = style.palette();
+        let ((left, top), cell) = self.gestures.dpad_layout();
+        let held = self.gestures.finger.as_ref().filter(|finger| finger.on_dpad).and_then(|finger| finger.held);
+
+        for (action, (column, row)) in DPAD_BUTTONS {
+            let (x, y) = (left + column * cell, top + row * cell);
+            self.context.set_fill_style_str(if held == Some(action) { palette.highlight } else { palette.panel });
+            self.context.fill_rect(x + 1.0, y + 1.0, cell - 2.0, cell - 2.0);
+
+            // ✅ An arrowhead pointing the button's way
+            let (dx, dy) = action.direction().map_or((0.0, 0.0), |(dx, dy)| (dx as f64, dy as f64));
+            let (cx, cy, r) = (x + cell / 2.0, y + cell / 2.0, cell / 4.0);
+            self.context.set_fill_style_str(palette.text);
+            self.context.begin_path();
+            self.context.move_to(cx + dx * r, cy + dy * r);
+            self.context.line_to(cx - dx * r - dy * r, cy - dy * r + dx * r);
+            self.context.line_to(cx - dx * r + dy * r, cy - dy * r - dx * r);
+            self.context.close_path();
+            self.context.fill();
+        }
+    }
+}
+
+impl Gestures {
+    fn down(&mut self, id: i32, (x, y): (f64, f64)) -> Option<Action> {
+        if self.taps {
+            return Some(Action::Flap);
+        }
+        if self.finger.is_some() {
+            return None;
+        }
+        let held = self.dpad_button((x, y));
+        self.finger = Some(Finger { id, origin: (x, y), held, on_dpad: held.is_some() });
+        held
+    }
+
+    fn moved(&mut self, id: i32, (x, y): (f64, f64)) -> Option<(Action, Option<Action>)> {
+        let on_dpad = self.finger.as_ref().is_some_and(|finger| finger.on_dpad);
+        let next = match on_dpad {
+            true => self.dpad_button((x, y)),
+            false => None,
+        };
+        let threshold = self.config.swipe_threshold;
+        let finger = self.finger.as_mut().filter(|finger| finger.id == id)?;
+
+        let action = if finger.on_dpad {
+            next?
+        } else {
+            let (dx, dy) = (x - finger.origin.0, y - finger.origin.1);
+            if dx.hypot(dy) < threshold {
+                return None;
+            }
+            finger.origin = (x, y); // ✅ The next swipe starts from here, for turns without lifting
+            match (dx.abs() > dy.abs(), dx > 0.0, dy > 0.0) {
+                (true, true, _) => Action::Right,
+                (true, false, _) => Action::Left,
+                (false, _, true) => Action::Down,
+                (false, _, false) => Action::Up,
+            }
+        };
+
+        if finger.held == Some(action) {
+            return None;
+        }
+        Some((action, finger.held.replace(action)))
+    }
+
+    fn up(&mut self, id: i32) -> Option<Action> {
+        if self.finger.as_ref().is_none_or(|finger| finger.id != id) {
+            return None;
+        }
+        self.finger.take()?.held
+    }
+
+    /// Top left corner and cell size of the D-pad's 3×3 grid.
+    fn dpad_layout(&self) -> ((f64, f64), f64) {
+        let (width, height) = self.size;
+        let size = width.min(height) * DPAD_SIZE;
+        ((width - size - DPAD_MARGIN, height - size - DPAD_MARGIN), size / 3.0)
+    }
+
+    fn dpad_button(&self, (x, y): (f64, f64)) -> Option<Action> {
+        if !self.config.dpad {
+            return None;
+        }
+        let ((left, top), cell) = self.dpad_layout();
+        let cell_at = ((x - left) / cell, (y - top) / cell);
+        DPAD_BUTTONS.iter().find_map(|&(action, (column, row))| {
+            let inside = (column..column + 1.0).contains(&cell_at.0) && (row..row + 1.0).contains(&cell_at.1);
+            inside.then_some(action)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 400×400 canvas: the D-pad's cells are 48 pixels, from (248, 248).
+    fn gestures(dpad: bool, taps: bool) -> Gestures {
+        Gestures { config: TouchConfig { swipe_threshold: 24.0, dpad }, size: (400.0, 400.0), taps, finger: None }
+    }
+
+    #[test]
+    fn swipes_hold_their_direction_until_the_finger_lifts() {
+        let mut touch = gestures(false, false);
+        assert_eq!(touch.down(1, (100.0, 100.0)), None);
+        assert_eq!(touch.moved(1, (110.0, 105.0)), None); // ✅ Short of the threshold
+
+        assert_eq!(touch.moved(1, (130.0, 105.0)), Some((Action::Right, None)));
+        assert_eq!(touch.moved(1, (160.0, 110.0)), None); // ✅ Still right
+        // ✅ Turning without lifting measures from where the last swipe ended
+        assert_eq!(touch.moved(1, (165.0, 80.0)), Some((Action::Up, Some(Action::Right))));
+
+        assert_eq!(touch.moved(2, (100.0, 300.0)), None); // ✅ A second finger is ignored
+        assert_eq!(touch.up(2), None);
+        assert_eq!(touch.up(1), Some(Action::Up));
+    }
+
+    #[test]
+    fn the_dpad_holds_the_button_under_the_finger() {
+        let mut touch = gestures(true, false);
+        assert_eq!(touch.down(1, (320.0, 272.0)), Some(Action::Up));
+        assert_eq!(touch.moved(1, (330.0, 280.0)), None);
+        assert_eq!(touch.moved(1, (368.0, 320.0)), Some((Action::Right, Some(Action::Up))));
+        assert_eq!(touch.moved(1, (320.0, 320.0)), None); // ✅ The middle isn't a button
+        assert_eq!(touch.up(1), Some(Action::Right));
+
+        // ✅ Off the D-pad, a finger swipes as usual
+        assert_eq!(touch.down(1, (100.0, 100.0)), None);
+        assert_eq!(touch.moved(1, (100.0, 140.0)), Some((Action::Down, None)));
+        assert_eq!(touch.up(1), Some(Action::Down));
+
+        let mut touch = gestures(false, false);
+        assert_eq!(touch.down(1, (320.0, 272.0)), None);
+    }
+
+    #[test]
+    fn every_tap_flaps() {
+        let mut touch = gestures(true, true);
+        for id in [1, 2, 1] {
+            assert_eq!(touch.down(id, (320.0, 272.0)), Some(Action::Flap));
+        }
+        assert_eq!(touch.moved(1, (100.0, 100.0)), None);
+        assert_eq!(touch.up(1), None);
+    }
+}
@@ -1,11 +1,17 @@
-use wasm_bindgen::prelude::*;
-use web_sys::{window, Document};
+use web_sys::{window, Element};
+
+/// Id of the page's live region, shared by every game on the page.
+const LIVE_REGION_ID: &str = "wasm-games-live";
+
+/// Keeps the element out of sight but in the accessibility tree.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; \
+                               border: 0; overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;";
 
 pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
-pub fn show_message(msg: &str) {
+pub fn show_message(msg: &str) -> Element {
     let window = window().unwrap();
     let document = window.document().unwrap();
     let body = document.body().unwrap();
@@ -13,4 +19,46 @@ pub fn show_message(msg: &str) {
     let message = document.create_element("p").unwrap();
     message.set_inner_html(msg);
     body.append_child(&message).unwrap();
+    message
+}
+
+/// Whether the reader's system asks for less animation.
+pub fn prefers_reduced_motion() -> bool {
+    window()
+        .unwrap()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// The reader's preferred languages, most preferred first, as a
+/// comma-separated list of tags.
+pub fn browser_languages() -> Option<String> {
+    let languages = window()?.navigator().languages();
+    let tags: Vec<String> = languages.iter().filter_map(|tag| tag.as_string()).collect();
+    (!tags.is_empty()).then(|| tags.join(","))
 }
+
+/// Has screen readers read `text` out, through a visually hidden
+/// `role="status"` live region added to the page on first use.
+pub fn announce(text: &str) {
+    let document = window().unwrap().document().unwrap();
+    let region = document.get_element_by_id(LIVE_REGION_ID).unwrap_or_else(|| {
+        let region = show_message("");
+        region.set_id(LIVE_REGION_ID);
+        region.set_attribute("role", "status").unwrap();
+        region.set_attribute("aria-live", "polite").unwrap();
+        region.set_attribute("aria-atomic", "true").unwrap();
+        region.set_attribute("style", VISUALLY_HIDDEN).unwrap();
+        region
+    });
+
+    // ✅ Unchanged text isn't read again, so vary repeats with a trailing space
+    let text = match region.text_content() {
+        Some(current) if current == text => format!("{}\u{a0}", text),
+        _ => text.to_string(),
+    };
+    region.set_text_content(Some(&text));
+}
+
@@ -0,0 +1,77 @@
+//! What a frame of each game looks like, as plain data: everything `render()`
+//! draws and nothing it doesn't. The games draw themselves from it and
+//! spectators (`spectate.rs`) receive it instead of the game.
+
+use serde::{Deserialize, Serialize};
+
+use crate::draw::Canvas;
+use crate::flappy::FlappyView;
+use crate::game::RunResult;
+use crate::pacman::PacmanView;
+use crate::snake::SnakeView;
+use crate::theme::Style;
+use crate::{flappy, pacman, snake};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "game", rename_all = "lowercase")]
+pub enum View {
+    Flappy(FlappyView),
+    Snake(SnakeView),
+    Pacman(PacmanView),
+}
+
+/// Score line and end-of-run banner, drawn by `game::draw_hud`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hud {
+    pub scores: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<RunResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_score: Option<u32>,
+}
+
+impl View {
+    /// Canvas size in pixels this frame is drawn at.
+    pub fn canvas_size(&self) -> (u32, u32) {
+        match self {
+            View::Flappy(_) => (flappy::WIDTH as u32, flappy::HEIGHT as u32),
+            View::Snake(view) => (
+                (view.width as f64 * snake::GRID_SIZE) as u32,
+                (view.height as f64 * snake::GRID_SIZE) as u32,
+            ),
+            View::Pacman(_) => (
+                (pacman::WIDTH as f64 * pacman::GRID_SIZE) as u32,
+                (pacman::HEIGHT as f64 * pacman::GRID_SIZE) as u32,
+            ),
+        }
+    }
+
+    pub fn draw(&self, context: &dyn Canvas, style: &Style) {
+        match self {
+            View::Flappy(view) => flappy::draw(context, view, None, style),
+            View::Snake(view) => snake::draw(context, view, None, style),
+            View::Pacman(view) => pacman::draw(context, view, None, style),
+        }
+    }
+}
+
+/// Positions only need to be good to a tenth of a pixel on screen, and
+/// shorter numbers keep spectator updates small.
+pub(crate) fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// `from` to `to` by `t` in 0..=1, for drawing between fixed ticks.
+pub(crate) fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+/// A grid position `t` of the way through a one-cell move. Longer jumps
+/// (wrapping round the board, respawning) snap to `to` instead of sliding
+/// across the screen.
+pub(crate) fn lerp_cell(from: (f64, f64), to: (f64, f64), t: f64) -> (f64, f64) {
+    if (to.0 - from.0).abs() > 1.0 || (to.1 - from.1).abs() > 1.0 {
+        return to;
+    }
+    (lerp(from.0, to.0, t), lerp(from.1, to.1, t))
+}
@@ -0,0 +1,30 @@
+//! The built-in bots: the greedy snake, keeping to a Hamiltonian cycle of
+//! the board with A* shortcuts, fills the board.
+
+use wasm_games::{agent, new_sim, GameConfig, GameEvent, TICK};
+
+#[test]
+fn greedy_snake_fills_the_board() {
+    let config = GameConfig::from_lookup(|key| match key {
+        "game" => Some("snake".to_string()),
+        "board" => Some("8x8".to_string()),
+        "seed" => Some("8".to_string()),
+        _ => None,
+    })
+    .unwrap();
+    let (mut sim, mut bot) = (new_sim(&config), agent("greedy", 0).unwrap());
+    for _ in 0..60 * 60 * 10 {
+        if let Some(command) = bot.act(&sim.view()) {
+            sim.command(command);
+        }
+        sim.update(TICK);
+        if let Some(GameEvent::GameOver { score }) =
+            sim.take_events().into_iter().find(|event| matches!(event, GameEvent::GameOver { .. }))
+        {
+            // ✅ Only once there's nowhere left to go
+            assert_eq!(score, 8 * 8 - 1, "the greedy snake died on {}", score);
+            return;
+        }
+    }
+    panic!("the greedy snake only got to {}", sim.scores()[0]);
+}
@@ -0,0 +1,84 @@
+//! Attract mode: the demo starts after the idle delay on the title screen,
+//! only there, never by itself with reduced motion, and any input takes it
+//! away. While it shows, "PRESS START" blinks (or stays lit) over a bot's run.
+
+use wasm_games::{Attract, DisplayList, DrawCommand, TICK};
+
+mod common;
+
+use common::config;
+
+/// Runs `seconds` of ticks, `waiting` on the title screen or not.
+fn idle(attract: &mut Attract, seconds: f64, waiting: bool) {
+    for _ in 0..(seconds / TICK).round() as u32 {
+        attract.update(TICK, waiting);
+    }
+}
+
+fn shows_press_start(attract: &Attract) -> bool {
+    let list = DisplayList::default();
+    attract.render(&list);
+    list.commands()
+        .iter()
+        .any(|command| matches!(command, DrawCommand::FillText { text, .. } if text == "PRESS START"))
+}
+
+#[test]
+fn demo_starts_after_the_idle_delay() {
+    let mut attract = Attract::new(&config("game=snake&attract=2")).unwrap();
+    assert_eq!(attract.delay(), 2.0);
+    idle(&mut attract, 1.9, true);
+    assert!(!attract.active());
+    idle(&mut attract, 0.2, true);
+    assert!(attract.active());
+
+    // ✅ Any input goes back to the title and is used up; the next one isn't
+    assert!(attract.input());
+    assert!(!attract.active());
+    assert!(!attract.input());
+    idle(&mut attract, 1.9, true);
+    assert!(!attract.active());
+}
+
+#[test]
+fn playing_resets_the_idle_time() {
+    let mut attract = Attract::new(&config("game=flappy&attract=2")).unwrap();
+    idle(&mut attract, 1.5, true);
+    idle(&mut attract, 10.0, false);
+    idle(&mut attract, 1.5, true);
+    assert!(!attract.active());
+    assert!(Attract::new(&config("game=flappy&attract=off")).is_none());
+}
+
+#[test]
+fn reduced_motion_never_starts_a_demo_by_itself() {
+    let mut attract = Attract::new(&config("game=pacman&attract=1&motion=reduced")).unwrap();
+    idle(&mut attract, 30.0, true);
+    assert!(!attract.active());
+
+    // ✅ Started on purpose, the prompt stays lit
+    attract.start();
+    for _ in 0..120 {
+        attract.update(TICK, true);
+        assert!(shows_press_start(&attract));
+    }
+}
+
+#[test]
+fn press_start_blinks_over_the_demo() {
+    let mut attract = Attract::new(&config("game=pacman&attract=1")).unwrap();
+    assert!(!shows_press_start(&attract));
+    idle(&mut attract, 1.0, true);
+    assert!(attract.active());
+
+    let (mut lit, mut dark) = (0, 0);
+    for _ in 0..120 {
+        attract.update(TICK, true);
+        if shows_press_start(&attract) {
+            lit += 1;
+        } else {
+            dark += 1;
+        }
+    }
+    assert!(lit >= 50 && dark >= 50, "lit {} ticks, dark {}", lit, dark);
+}
@@ -0,0 +1,86 @@
+//! Challenge tokens: a finished run's token opens the same game, settings and
+//! seed with its score to beat, and anything that isn't a token made that way
+//! is turned away like any other bad option.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use wasm_games::{new_sim, Command, ConfigError, TICK};
+
+mod common;
+
+use common::parse;
+
+const OPTIONS: &str = "game=snake&difficulty=hard&speed=0.75&board=12x10&seed=7";
+
+/// Steers a snake into the wall and returns the token for that run and its score.
+fn finished_run() -> (String, u32) {
+    let mut sim = new_sim(&parse(OPTIONS).unwrap());
+    sim.command(Command::Steer(0, 1, 0));
+    for _ in 0..10_000 {
+        sim.update(TICK);
+        if let Some(token) = sim.challenge_token() {
+            return (token, sim.scores()[0]);
+        }
+    }
+    panic!("the snake never hit the wall");
+}
+
+fn rejected(token: &str) -> bool {
+    matches!(parse(&format!("challenge={}", token)), Err(ConfigError::Invalid { param: "challenge", .. }))
+}
+
+/// The token's bytes with `change` applied, encoded again.
+fn tampered(token: &str, change: impl FnOnce(&mut Vec<u8>)) -> String {
+    let mut bytes = URL_SAFE_NO_PAD.decode(token).unwrap();
+    change(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[test]
+fn token_roundtrips_settings_seed_and_score() {
+    let (token, score) = finished_run();
+    let original = parse(OPTIONS).unwrap();
+    let decoded = parse(&format!("challenge={}", token)).unwrap();
+
+    assert_eq!(decoded.game(), original.game());
+    assert_eq!(decoded.seed(), Some(7));
+    assert_eq!(decoded.snake(), original.snake());
+    assert_eq!(decoded.challenge_score(), Some(score));
+}
+
+#[test]
+fn wrong_length_is_rejected() {
+    let (token, _) = finished_run();
+    assert!(rejected(&tampered(&token, |bytes| {
+        bytes.pop();
+    })));
+    assert!(rejected(&tampered(&token, |bytes| bytes.push(0))));
+    assert!(rejected(""));
+}
+
+#[test]
+fn bad_base64_is_rejected() {
+    let (token, _) = finished_run();
+    assert!(rejected(&token.replacen(|_| true, "!", 1)));
+    assert!(rejected("not a token"));
+    assert!(rejected(&format!("{}==", token)));
+}
+
+#[test]
+fn out_of_range_fields_are_rejected() {
+    let (token, _) = finished_run();
+    // ✅ (field, byte, value)
+    let fields = [
+        ("version", 0, 2),
+        ("game", 1, 3),
+        ("difficulty", 2, 3),
+        ("mode", 3, 2),
+        ("maze", 4, 3),
+        ("speed", 6, 0xff),
+        ("board", 7, 200),
+        ("board for flappy", 1, 0),
+    ];
+    for (field, byte, value) in fields {
+        assert!(rejected(&tampered(&token, |bytes| bytes[byte] = value)), "{}", field);
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)] // ✅ Each test binary uses its own share of these
+
+use wasm_games::{ConfigError, GameConfig};
+
+/// A config from query-string pairs such as `game=snake&seed=3`, the first
+/// of any repeated key winning.
+pub fn parse(query: &str) -> Result<GameConfig, ConfigError> {
+    let params: Vec<(&str, &str)> = query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+    GameConfig::from_lookup(|key| params.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string()))
+}
+
+/// `parse` for a query the test knows is valid.
+pub fn config(query: &str) -> GameConfig {
+    parse(query).unwrap()
+}
@@ -0,0 +1,79 @@
+//! The RL environment: rewards add up to the score the run reached, less one
+//! for the step that ends it, and `done` comes at a game over or after
+//! `max_steps`, whichever is first.
+
+use wasm_games::{new_sim, Command, Env, GameEvent, TICK};
+
+mod common;
+
+use common::config;
+
+const SEED: u64 = 21;
+
+/// Plays `actions` (the last one repeating) until `done`; returns each step's reward.
+fn episode(env: &mut Env, actions: &[u32]) -> Vec<f64> {
+    env.reset(SEED);
+    let mut rewards = Vec::new();
+    for i in 0.. {
+        let step = env.step(actions[i.min(actions.len() - 1)]);
+        rewards.push(step.reward);
+        if step.done {
+            return rewards;
+        }
+    }
+    unreachable!()
+}
+
+/// The score a run ends on when the same commands go straight to the sim.
+fn final_score(options: &str, ticks_per_step: u32, commands: &[Option<Command>]) -> u32 {
+    let mut sim = new_sim(&config(&format!("{}&seed={}", options, SEED)));
+    for i in 0.. {
+        if let Some(command) = commands[i.min(commands.len() - 1)] {
+            sim.command(command);
+        }
+        for _ in 0..ticks_per_step {
+            sim.update(TICK);
+            for event in sim.take_events() {
+                if let GameEvent::GameOver { score } = event {
+                    return score;
+                }
+            }
+        }
+    }
+    unreachable!()
+}
+
+#[test]
+fn game_over_costs_one_point() {
+    // ✅ Snake heads right into the wall; the bird flaps once and falls
+    let cases = [
+        ("game=snake&board=12x10", vec![4], vec![Some(Command::Steer(0, 1, 0)), None]),
+        ("game=flappy", vec![1, 0], vec![Some(Command::Flap(0)), None]),
+    ];
+    for (options, actions, commands) in cases {
+        let mut env = Env::new(&config(options), "features").unwrap();
+        let rewards = episode(&mut env, &actions);
+        let score = final_score(options, env.ticks_per_step(), &commands);
+
+        assert_eq!(rewards.iter().sum::<f64>(), score as f64 - 1.0, "{}", options);
+        assert!(rewards[..rewards.len() - 1].iter().all(|&reward| reward >= 0.0), "{}", options);
+    }
+}
+
+#[test]
+fn max_steps_ends_an_idle_run() {
+    let mut env = Env::new(&config("game=snake"), "grid").unwrap();
+    env.set_max_steps(5);
+    let rewards = episode(&mut env, &[0]);
+    assert_eq!(rewards, vec![0.0; 5]);
+
+    // ✅ Starting over counts from zero again
+    let rewards = episode(&mut env, &[0]);
+    assert_eq!(rewards.len(), 5);
+}
+
+#[test]
+fn versus_games_and_unknown_observations_are_refused() {
+    assert!(Env::new(&config("game=snake&players=2"), "grid").is_err());
+    assert!(Env::new(&config("game=snake"), "pixels").is_err());
+}
@@ -0,0 +1,132 @@
+//! Randomized invariant checks: each game, on many seeds and settings, is
+//! driven with random inputs while rule invariants are checked after every
+//! tick. A failure names the settings, seed and tick to reproduce it with.
+//! `INVARIANT_RUNS=N` plays more (or fewer) runs per setting.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use wasm_games::{new_sim, Command, GameConfig, GameEvent, Sim, View, TICK};
+
+mod common;
+
+const DEFAULT_RUNS: u64 = 12;
+const TICKS: u32 = 3_000;
+/// Chance per tick of an input, so runs mix steady stretches with bursts.
+const INPUT_CHANCE: f64 = 0.15;
+const DIRECTIONS: [(i32, i32); 5] = [(0, -1), (0, 1), (-1, 0), (1, 0), (0, 0)];
+
+fn config(options: &str, seed: u64) -> GameConfig {
+    common::config(&format!("seed={}&{}", seed, options))
+}
+
+fn random_command(rng: &mut StdRng, view: &View, players: usize) -> Command {
+    let player = rng.gen_range(0..players);
+    match view {
+        View::Flappy(_) => Command::Flap(player),
+        View::Snake(_) | View::Pacman(_) => {
+            let (dx, dy) = DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())];
+            Command::Steer(player, dx, dy)
+        }
+    }
+}
+
+/// Plays every seed of `options` with random inputs, calling `check` with
+/// the views before and after each tick and that tick's events.
+fn check_runs(options: &str, check: impl Fn(&View, &View, &[GameEvent]) -> Result<(), String>) {
+    let runs = std::env::var("INVARIANT_RUNS").ok().and_then(|runs| runs.parse().ok()).unwrap_or(DEFAULT_RUNS);
+    for seed in 0..runs {
+        let config = config(options, seed);
+        let mut sim: Box<dyn Sim> = new_sim(&config);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut before = sim.view();
+        if let Err(message) = check(&before, &before, &[]) {
+            panic!("{} seed={} at start: {}", options, seed, message);
+        }
+
+        for tick in 0..TICKS {
+            let mut events = Vec::new();
+            if rng.gen_bool(INPUT_CHANCE) {
+                sim.command(random_command(&mut rng, &before, config.players()));
+                events.extend(sim.take_events());
+            }
+            sim.update(TICK);
+            events.extend(sim.take_events());
+
+            let after = sim.view();
+            if let Err(message) = check(&before, &after, &events) {
+                panic!("{} seed={} tick={}: {}", options, seed, tick, message);
+            }
+            before = after;
+        }
+    }
+}
+
+#[test]
+fn flappy_pipe_gaps_stay_on_screen() {
+    for options in ["game=flappy", "game=flappy&players=2&difficulty=hard", "game=flappy&difficulty=easy&speed=2"] {
+        check_runs(options, |_, after, _| {
+            let View::Flappy(view) = after else { unreachable!() };
+            let (width, height) = after.canvas_size();
+            for &(x, gap_y) in &view.pipes {
+                if gap_y < 0.0 || gap_y + view.pipe_gap > height as f64 {
+                    return Err(format!("pipe at x={} has its gap at {}..{}", x, gap_y, gap_y + view.pipe_gap));
+                }
+                if x > width as f64 {
+                    return Err(format!("pipe at x={} is past the right edge", x));
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+#[test]
+fn snake_bodies_and_food() {
+    for options in ["game=snake", "game=snake&board=8", "game=snake&board=8&mode=wrap", "game=snake&players=2&board=10"] {
+        check_runs(options, |_, after, _| {
+            let View::Snake(view) = after else { unreachable!() };
+            let mut cells = Vec::new();
+            for (player, body) in view.snakes.iter().enumerate() {
+                for &(x, y) in body {
+                    if x >= view.width || y >= view.height {
+                        return Err(format!("snake {} is off the board at {:?}", player + 1, (x, y)));
+                    }
+                    if cells.contains(&(x, y)) {
+                        return Err(format!("two segments at {:?}: {:?}", (x, y), view.snakes));
+                    }
+                    cells.push((x, y));
+                }
+            }
+            if cells.contains(&view.food) {
+                return Err(format!("food at {:?} is on a snake: {:?}", view.food, view.snakes));
+            }
+            Ok(())
+        });
+    }
+}
+
+#[test]
+fn pacman_walls_and_pellets() {
+    for options in ["game=pacman", "game=pacman&maze=corridors", "game=pacman&maze=open", "game=pacman&players=2"] {
+        check_runs(options, |before, after, events| {
+            let (View::Pacman(before), View::Pacman(after)) = (before, after) else { unreachable!() };
+            let wall = |(x, y): (usize, usize)| after.rows[y].as_bytes()[x] == b'#';
+            if wall(after.pacman) {
+                return Err(format!("Pac-Man is in a wall at {:?}", after.pacman));
+            }
+            if let Some(&ghost) = after.ghosts.iter().find(|&&ghost| wall(ghost)) {
+                return Err(format!("a ghost is in a wall at {:?}", ghost));
+            }
+
+            // ✅ A new maze (cleared, or a new run) fills the pellets back up
+            let new_maze = events
+                .iter()
+                .any(|event| matches!(event, GameEvent::MazeCleared { .. } | GameEvent::GameOver { .. }));
+            let pellets = |rows: &[String]| rows.iter().flat_map(|row| row.bytes()).filter(|&c| c == b'.' || c == b'o').count();
+            if !new_maze && pellets(&after.rows) > pellets(&before.rows) {
+                return Err(format!("pellets went up from {} to {}", pellets(&before.rows), pellets(&after.rows)));
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,83 @@
+//! The string tables: the compiler checks every locale has every key, so
+//! this checks what it can't, that translations keep their `{}` slots, and
+//! that the chosen language reaches every front end.
+
+use serde_json::Value;
+use wasm_games::{fill, new_sim, term, Command, GameConfig, Language, TICK};
+
+/// Every template in `language`'s table, by key path.
+fn templates(language: Language) -> Vec<(String, String)> {
+    fn walk(path: String, value: Value, out: &mut Vec<(String, String)>) {
+        match value {
+            Value::String(text) => out.push((path, text)),
+            Value::Array(items) => {
+                for (i, item) in items.into_iter().enumerate() {
+                    walk(format!("{}[{}]", path, i), item, out);
+                }
+            }
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    walk(format!("{}.{}", path, key), field, out);
+                }
+            }
+            _ => panic!("unexpected value at {}", path),
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(String::new(), serde_json::to_value(language.strings()).unwrap(), &mut out);
+    out
+}
+
+#[test]
+fn translations_keep_every_placeholder() {
+    let english = templates(Language::En);
+    for language in &Language::ALL[1..] {
+        for ((key, text), (_, original)) in templates(*language).iter().zip(&english) {
+            assert!(!text.is_empty(), "{} is empty in {}", key, language.code());
+            assert_eq!(
+                text.matches("{}").count(),
+                original.matches("{}").count(),
+                "{} in {}: {:?} vs {:?}",
+                key,
+                language.code(),
+                text,
+                original,
+            );
+        }
+    }
+}
+
+#[test]
+fn preferences_fall_back_to_a_bundled_language() {
+    assert_eq!(Language::negotiate("pt-BR"), Language::Pt);
+    assert_eq!(Language::negotiate("fr-FR,es-419,en"), Language::Es);
+    assert_eq!(Language::negotiate("de, ja"), Language::En);
+    assert_eq!(Language::negotiate(""), Language::En);
+}
+
+#[test]
+fn fill_puts_values_in_order() {
+    let strings = Language::Es.strings();
+    assert_eq!(fill(strings.player_points, &[&2, &30]), "Jugador 2: 30 puntos.");
+    assert_eq!(fill(Language::En.strings().waiting_for_channel, &[&"{}"]), "Waiting for \"{}\"...");
+}
+
+#[test]
+fn terminal_shows_the_chosen_language() {
+    let config = GameConfig::from_lookup(|key| match key {
+        "game" => Some("flappy".to_string()),
+        "lang" => Some("es".to_string()),
+        _ => None,
+    })
+    .unwrap();
+    let mut sim = new_sim(&config);
+    sim.command(Command::Flap(0));
+    for _ in 0..240 {
+        sim.update(TICK); // ✅ Down to the ground and the end-of-run banner
+    }
+
+    let screen = term::render(&sim.view(), &config.style());
+    assert!(screen.starts_with("Puntos: 0"), "{}", screen);
+    assert!(screen.contains("FIN DEL JUEGO"), "{}", screen);
+}
@@ -0,0 +1,102 @@
+//! Lockstep scheduling: local input runs `delay` ticks late, nobody moves on
+//! without every player's input for the tick, late or repeated inputs change
+//! nothing, and peers' state hashes part ways as soon as their sims do.
+
+use wasm_games::{GameConfig, Lockstep, SnakeSim, HASH_INTERVAL, TICK};
+
+const DELAY: u32 = 3;
+const RIGHT: Option<(i32, i32)> = Some((1, 0));
+const DOWN: Option<(i32, i32)> = Some((0, 1));
+
+fn snake_sim() -> SnakeSim {
+    let config = GameConfig::from_lookup(|key| match key {
+        "game" => Some("snake".to_string()),
+        "players" => Some("2".to_string()),
+        "seed" => Some("3".to_string()),
+        _ => None,
+    })
+    .unwrap();
+    SnakeSim::new(&config)
+}
+
+#[test]
+fn local_input_runs_delay_ticks_later() {
+    let mut lockstep = Lockstep::new(1, 0, DELAY);
+    assert!(lockstep.can_schedule());
+    assert_eq!(lockstep.schedule(RIGHT), DELAY);
+    assert!(!lockstep.can_schedule());
+
+    // ✅ The ticks before it run with nobody pressing anything
+    for tick in 0..DELAY {
+        assert_eq!(lockstep.tick(), tick);
+        assert_eq!(lockstep.advance(), Some(vec![None]));
+        assert!(lockstep.can_schedule());
+        lockstep.schedule(None);
+    }
+    assert_eq!(lockstep.advance(), Some(vec![RIGHT]));
+}
+
+#[test]
+fn stalls_until_every_peer_has_sent_the_tick() {
+    let mut lockstep = Lockstep::new(2, 0, 0);
+    lockstep.schedule(RIGHT);
+    assert_eq!(lockstep.advance(), None);
+    assert_eq!(lockstep.advance(), None);
+    assert_eq!(lockstep.tick(), 0);
+
+    // ✅ The other peer's later tick alone doesn't unblock this one
+    lockstep.receive(1, 1, DOWN);
+    assert_eq!(lockstep.advance(), None);
+
+    lockstep.receive(1, 0, None);
+    assert_eq!(lockstep.advance(), Some(vec![RIGHT, None]));
+    assert_eq!(lockstep.tick(), 1);
+    assert_eq!(lockstep.advance(), None);
+    lockstep.schedule(None);
+    assert_eq!(lockstep.advance(), Some(vec![None, DOWN]));
+}
+
+#[test]
+fn stale_and_repeated_inputs_are_ignored() {
+    let mut lockstep = Lockstep::new(2, 0, DELAY);
+    lockstep.receive(1, DELAY, DOWN);
+    lockstep.receive(1, DELAY, RIGHT);
+    for _ in 0..DELAY {
+        // ✅ The opening ticks are already settled for everyone
+        lockstep.receive(1, lockstep.tick(), RIGHT);
+        assert_eq!(lockstep.advance(), Some(vec![None, None]));
+    }
+
+    lockstep.schedule(None);
+    lockstep.schedule(None);
+    assert_eq!(lockstep.advance(), Some(vec![None, DOWN]));
+
+    // ✅ Too late for the tick that just ran, and a second go at the next one
+    lockstep.receive(1, DELAY, RIGHT);
+    assert_eq!(lockstep.advance(), None);
+    lockstep.receive(0, DELAY + 1, RIGHT);
+    lockstep.receive(1, DELAY + 1, None);
+    assert_eq!(lockstep.advance(), Some(vec![None, None]));
+
+    // ✅ Players that aren't in the game
+    lockstep.receive(2, lockstep.tick(), DOWN);
+    assert_eq!(lockstep.advance(), None);
+}
+
+#[test]
+fn peers_agree_until_their_sims_diverge() {
+    let (mut left, mut right) = (snake_sim(), snake_sim());
+    for tick in 1..=HASH_INTERVAL * 4 {
+        if tick == HASH_INTERVAL * 2 {
+            left.change_direction(0, 0, 1);
+            right.change_direction(0, 0, -1);
+        }
+        left.update(TICK);
+        right.update(TICK);
+
+        if tick % HASH_INTERVAL == 0 {
+            let diverged = tick >= HASH_INTERVAL * 2;
+            assert_eq!(left.state_hash() != right.state_hash(), diverged, "tick {}", tick);
+        }
+    }
+}
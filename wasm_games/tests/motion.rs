@@ -0,0 +1,93 @@
+//! Frames drawn between ticks: whatever moved slides from where it was a tick
+//! ago, except across a jump. A snake wrapping round the board snaps to the
+//! far side, and a pipe that just appeared isn't slid in from the one that
+//! left the screen.
+
+use wasm_games::{agent, new_sim, Command, DisplayList, DrawCommand, Sim, Style, View, TICK};
+
+mod common;
+
+use common::config;
+
+const ALPHA: f64 = 0.5;
+
+/// The rectangles `sim` draws in `color`, `ALPHA` of a tick past its last update.
+fn rects(sim: &dyn Sim, color: &str) -> Vec<(f64, f64, f64, f64)> {
+    let list = DisplayList::default();
+    sim.draw_at(&list, ALPHA, &Style::default());
+    let mut fill = String::new();
+    let mut rects = Vec::new();
+    for command in list.commands() {
+        match command {
+            DrawCommand::FillStyle(color) => fill = color,
+            DrawCommand::FillRect { x, y, width, height } if fill == color => rects.push((x, y, width, height)),
+            _ => {}
+        }
+    }
+    rects
+}
+
+#[test]
+fn snake_snaps_across_a_wrap() {
+    let mut sim = new_sim(&config("game=snake&mode=wrap&board=10x8&seed=1"));
+    let color = Style::default().palette().snakes[0];
+    sim.command(Command::Steer(0, 1, 0));
+
+    let mut wraps = 0;
+    let mut last_head = None;
+    for _ in 0..600 {
+        sim.update(TICK);
+        let View::Snake(view) = sim.view() else { unreachable!() };
+        let grid = sim.view().canvas_size().0 as f64 / view.width as f64;
+        let body = &view.snakes[0];
+
+        // ✅ Every segment within a cell of where it is now, never halfway across the board
+        let drawn = rects(sim.as_ref(), color);
+        assert_eq!(drawn.len(), body.len());
+        for (&(x, y, _, _), &(cell_x, cell_y)) in drawn.iter().zip(body) {
+            assert!((x / grid - cell_x as f64).abs() <= 1.0, "{} drawn for cell {}", x / grid, cell_x);
+            assert!((y / grid - cell_y as f64).abs() <= 1.0, "{} drawn for cell {}", y / grid, cell_y);
+        }
+
+        let head = body[0];
+        if last_head.is_some_and(|(x, _)| x == view.width - 1) && head.0 == 0 {
+            wraps += 1;
+            assert_eq!(drawn[0].0, 0.0, "the head should snap to the left edge");
+        }
+        last_head = Some(head);
+    }
+    assert!(wraps >= 2, "the snake only wrapped {} times", wraps);
+}
+
+#[test]
+fn new_pipes_are_not_slid_in() {
+    let config = config("game=flappy&seed=2");
+    let step = config.flappy().pipe_speed * TICK;
+    let mut sim = new_sim(&config);
+    let mut bot = agent("greedy", 0).unwrap();
+    let color = Style::default().palette().pipes;
+    let View::Flappy(first) = sim.view() else { unreachable!() };
+    let mut gaps: Vec<f64> = first.pipes.iter().map(|&(_, gap_y)| gap_y).collect();
+
+    let mut new_pipes = 0;
+    for _ in 0..60 * 20 {
+        if let Some(command) = bot.act(&sim.view()) {
+            sim.command(command);
+        }
+        sim.update(TICK);
+        let View::Flappy(view) = sim.view() else { unreachable!() };
+        let drawn = rects(sim.as_ref(), color);
+
+        // ✅ A pipe's top half is drawn from the top of the screen down to its gap
+        for &(x, gap_y) in &view.pipes {
+            let &(drawn_x, ..) = drawn.iter().find(|&&(_, y, _, height)| y == 0.0 && height == gap_y).unwrap();
+            assert!((drawn_x - x).abs() <= step, "pipe at {} drawn at {}", x, drawn_x);
+            if !gaps.contains(&gap_y) {
+                new_pipes += 1;
+                assert_eq!(drawn_x, x, "a new pipe starts where it appeared");
+            }
+        }
+        gaps = view.pipes.iter().map(|&(_, gap_y)| gap_y).collect();
+    }
+    assert!(new_pipes >= 3, "only {} pipes appeared", new_pipes);
+}
@@ -0,0 +1,44 @@
+//! Flappy physics runs in fixed substeps, so the step size a host updates
+//! with must not change how a run plays out.
+
+use wasm_games::{FlappySim, GameConfig, GameEvent, View};
+
+/// Flap every two thirds of a second, which keeps the bird roughly level.
+const FLAP_EVERY: f64 = 2.0 / 3.0;
+const SECONDS: u32 = 20;
+/// Every rate tested divides into this one, so all of them see each frame.
+const SAMPLE_HZ: u32 = 30;
+
+/// The run at each `1 / SAMPLE_HZ` boundary, updating `hz` times a second.
+fn run(hz: u32) -> (Vec<View>, Vec<GameEvent>) {
+    let config = GameConfig::from_lookup(|key| (key == "seed").then(|| "7".to_string())).unwrap();
+    let mut sim = FlappySim::new(&config);
+    let flap_steps = (FLAP_EVERY * hz as f64).round() as u32;
+    let (mut frames, mut events) = (Vec::new(), Vec::new());
+
+    for step in 0..SECONDS * hz {
+        if step % flap_steps == 0 {
+            sim.flap(0);
+        }
+        sim.update(1.0 / hz as f64);
+        events.extend(sim.take_events());
+        if (step + 1) % (hz / SAMPLE_HZ) == 0 {
+            frames.push(View::Flappy(sim.view()));
+        }
+    }
+    (frames, events)
+}
+
+#[test]
+fn flappy_plays_out_the_same_at_any_update_rate() {
+    let (frames, events) = run(60);
+    assert!(events.iter().any(|event| matches!(event, GameEvent::PipePassed { .. })));
+
+    for hz in [30, 120, 240] {
+        let (other_frames, other_events) = run(hz);
+        assert_eq!(other_events, events, "events at {} Hz", hz);
+        for (i, (frame, other)) in frames.iter().zip(&other_frames).enumerate() {
+            assert_eq!(other, frame, "frame {} at {} Hz", i, hz);
+        }
+    }
+}
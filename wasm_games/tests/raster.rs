@@ -0,0 +1,20 @@
+//! The software rasterizer draws recorded frames where the canvas would.
+
+use wasm_games::raster::Image;
+use wasm_games::{new_sim, Command, GameConfig, GameKind, Style, TICK};
+
+#[test]
+fn flappy_frame_pixels() {
+    let mut sim = new_sim(&GameConfig::new(GameKind::Flappy));
+    sim.command(Command::Flap(0));
+    sim.update(TICK);
+
+    let image = Image::render(&sim.view(), Style::default());
+    assert_eq!((image.width, image.height), (400, 600));
+    assert_eq!(image.pixel(50, 300), [255, 255, 0, 255], "bird");
+    assert_eq!(image.pixel(200, 500), [0, 0, 0, 255], "background");
+
+    let mut png = Vec::new();
+    image.write_png(&mut png).unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+}
@@ -0,0 +1,130 @@
+//! Leaderboard replays: a run recorded as it's played verifies with its
+//! score, and a replay that claims more, or whose inputs couldn't have come
+//! from a real run, is turned away.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use wasm_games::{new_sim, Command, GameConfig, Recorder, Replay, ReplayError, Sim, MAX_TICKS, TICK};
+
+/// Flaps for a while and then lets the bird fall, recording the run the way
+/// the game shell does.
+fn recorded_run(seed: u64) -> Replay {
+    let config = GameConfig::from_lookup(|key| match key {
+        "game" => Some("flappy".to_string()),
+        "seed" => Some(seed.to_string()),
+        _ => None,
+    })
+    .unwrap();
+    let (mut sim, mut recorder) = (new_sim(&config), Recorder::new());
+    let flap = |sim: &mut dyn Sim, recorder: &mut Recorder| {
+        sim.command(Command::Flap(0));
+        recorder.events(&sim.take_events(), sim);
+        recorder.command(Command::Flap(0));
+    };
+
+    flap(sim.as_mut(), &mut recorder);
+    for tick in 1..MAX_TICKS {
+        if tick < 600 && tick % 24 == 0 {
+            flap(sim.as_mut(), &mut recorder);
+        }
+        sim.update(TICK);
+        recorder.events(&sim.take_events(), sim.as_ref());
+        recorder.tick();
+        if let Some(replay) = recorder.last() {
+            return replay.clone();
+        }
+    }
+    panic!("the run never ended");
+}
+
+/// The replay with its token claiming `score` instead.
+fn claiming(replay: &Replay, score: u32) -> Replay {
+    let mut bytes = URL_SAFE_NO_PAD.decode(&replay.challenge).unwrap();
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&score.to_le_bytes());
+    Replay { challenge: URL_SAFE_NO_PAD.encode(bytes), inputs: replay.inputs.clone() }
+}
+
+#[test]
+fn recorded_run_verifies() {
+    let replay = recorded_run(11);
+    assert!(replay.inputs.len() > 1);
+    let verified = replay.verify().unwrap();
+    assert_eq!(verified.seed, 11);
+
+    // ✅ And survives the trip to the server
+    let sent: Replay = serde_json::from_str(&serde_json::to_string(&replay).unwrap()).unwrap();
+    assert_eq!(sent.verify(), Ok(verified));
+}
+
+#[test]
+fn tampered_score_is_rejected() {
+    let replay = recorded_run(12);
+    let actual = replay.verify().unwrap().score;
+    assert_eq!(
+        claiming(&replay, actual + 5).verify(),
+        Err(ReplayError::ScoreMismatch { claimed: actual + 5, actual })
+    );
+}
+
+#[test]
+fn out_of_order_inputs_are_rejected() {
+    let mut replay = recorded_run(13);
+    let (first, second) = (replay.inputs[1].0, replay.inputs[2].0);
+    replay.inputs.swap(1, 2);
+    assert!(second > first);
+    assert_eq!(replay.verify(), Err(ReplayError::OutOfOrder { tick: first }));
+}
+
+#[test]
+fn inputs_past_the_end_are_rejected() {
+    let replay = recorded_run(14);
+
+    // ✅ After the bird hit the ground, and after the longest run allowed
+    let mut late = replay.clone();
+    late.inputs.push((MAX_TICKS - 1, Command::Flap(0)));
+    assert_eq!(late.verify(), Err(ReplayError::AfterEnd { tick: MAX_TICKS - 1 }));
+
+    let mut too_long = replay;
+    too_long.inputs.push((MAX_TICKS, Command::Flap(0)));
+    assert_eq!(too_long.verify(), Err(ReplayError::TooLong));
+}
+
+#[test]
+fn inputs_the_run_cant_take_are_rejected() {
+    let replay = recorded_run(15);
+
+    // ✅ A second bird that isn't there, and steering, which a bird can't do
+    for command in [Command::Flap(1), Command::Steer(0, 0, 1), Command::Steer(0, 0, 0)] {
+        let mut padded = replay.clone();
+        padded.inputs.insert(1, (padded.inputs[1].0, command));
+        let tick = padded.inputs[1].0;
+        assert_eq!(padded.verify(), Err(ReplayError::BadInput { tick }), "{:?}", command);
+    }
+}
+
+#[test]
+fn runs_that_never_start_or_end_are_cut_short() {
+    let replay = recorded_run(16);
+    let idle = Replay { challenge: replay.challenge.clone(), inputs: Vec::new() };
+    assert_eq!(idle.verify(), Err(ReplayError::NeverStarted));
+    let late = Replay { challenge: replay.challenge.clone(), inputs: vec![(1, Command::Flap(0))] };
+    assert_eq!(late.verify(), Err(ReplayError::NeverStarted));
+
+    // ✅ A snake on a board that wraps, heading straight on forever
+    let mut bytes = URL_SAFE_NO_PAD.decode(snake_token()).unwrap();
+    bytes[3] = 1;
+    let endless = Replay { challenge: URL_SAFE_NO_PAD.encode(bytes), inputs: vec![(0, Command::Steer(0, 1, 0))] };
+    assert_eq!(endless.verify(), Err(ReplayError::NeverEnded));
+}
+
+/// Token of a classic snake run that went straight into the wall.
+fn snake_token() -> String {
+    let config = GameConfig::from_lookup(|key| (key == "game").then(|| "snake".to_string())).unwrap();
+    let mut sim = new_sim(&config);
+    sim.command(Command::Steer(0, 1, 0));
+    while sim.challenge_token().is_none() {
+        sim.update(TICK);
+    }
+    sim.challenge_token().unwrap()
+}
@@ -0,0 +1,60 @@
+//! Accessibility settings: slow mode slows every game by the same factor, and
+//! reduced motion is a drawing preference like the theme.
+
+use wasm_games::{new_sim, Command, GameConfig, View, TICK};
+
+mod common;
+
+use common::config;
+
+#[test]
+fn slow_mode_scales_every_game_uniformly() {
+    for speed in ["0.5", "0.75"] {
+        let factor: f64 = speed.parse().unwrap();
+        let (full, slow) = (config("game=flappy"), config(&format!("game=flappy&speed={}", speed)));
+        assert_eq!(slow.flappy().pipe_speed, full.flappy().pipe_speed * factor);
+        assert_eq!(slow.flappy().jump_strength, full.flappy().jump_strength * factor);
+        assert_eq!(slow.flappy().max_fall_speed, full.flappy().max_fall_speed * factor);
+        assert_eq!(slow.flappy().gravity, full.flappy().gravity * factor * factor);
+
+        let (full, slow) = (config("game=snake"), config(&format!("game=snake&speed={}", speed)));
+        assert_eq!(slow.snake().move_interval, full.snake().move_interval / factor);
+
+        let (full, slow) = (config("game=pacman"), config(&format!("game=pacman&speed={}", speed)));
+        assert_eq!(slow.pacman().pacman_move_interval, full.pacman().pacman_move_interval / factor);
+        assert_eq!(slow.pacman().ghost_move_interval, full.pacman().ghost_move_interval / factor);
+    }
+}
+
+#[test]
+fn slow_flaps_climb_as_high_but_take_longer() {
+    // ✅ Ticks to the top of a flap, and how high it got
+    let peak = |query: &str| {
+        let mut sim = new_sim(&config(query));
+        sim.command(Command::Flap(0));
+        let mut heights = Vec::new();
+        for _ in 0..120 {
+            sim.update(TICK);
+            let View::Flappy(view) = sim.view() else { unreachable!() };
+            heights.push(view.birds[0].unwrap());
+        }
+        let top = heights.iter().copied().fold(f64::INFINITY, f64::min);
+        (heights.iter().position(|&y| y == top).unwrap(), top)
+    };
+
+    let (full_ticks, full_top) = peak("game=flappy&seed=1");
+    let (slow_ticks, slow_top) = peak("game=flappy&seed=1&speed=0.5");
+    assert!(slow_ticks.abs_diff(full_ticks * 2) <= 1, "{} ticks at full speed, {} slow", full_ticks, slow_ticks);
+    assert!((slow_top - full_top).abs() < 1.0, "top at {} at full speed, {} slow", full_top, slow_top);
+}
+
+#[test]
+fn reduced_motion_is_a_style_option() {
+    assert!(!config("game=snake").style().reduced_motion);
+    assert!(config("game=snake&motion=reduced").style().reduced_motion);
+    assert!(config("game=snake&motion=reduce").style().reduced_motion);
+    assert!(!config("game=snake&motion=full").style().reduced_motion);
+
+    let err = GameConfig::from_lookup(|key| (key == "motion").then(|| "slow".to_string())).unwrap_err();
+    assert!(err.to_string().contains("motion"));
+}
@@ -0,0 +1,51 @@
+//! The `simulate` batch runner: a small batch of each game, checked against
+//! its own per-run report, and the same runs however many threads play them.
+
+use std::process::Command;
+
+use serde_json::Value;
+
+const RUNS: usize = 6;
+
+/// `simulate`'s JSON report for `args`.
+fn simulate(args: &[&str]) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_simulate"))
+        .args(["--runs", &RUNS.to_string(), "--seed", "40", "--max-seconds", "30"])
+        .args(args)
+        .output()
+        .expect("simulate runs");
+    assert!(output.status.success(), "simulate {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).expect("simulate prints JSON")
+}
+
+#[test]
+fn the_summary_matches_the_runs() {
+    // ✅ A narrow gap so the flappy bot's runs don't all time out on the same score
+    for (game, tuning) in [("flappy", &["--pipe-gap", "125"][..]), ("snake", &[]), ("pacman", &[])] {
+        let report = simulate(&[&[format!("game={}", game).as_str(), "--threads", "2"], tuning].concat());
+        let (summary, runs) = (&report["summary"], report["runs"].as_array().unwrap());
+
+        assert_eq!(summary["game"], game);
+        assert_eq!(summary["runs"], RUNS);
+        assert_eq!(runs.len(), RUNS);
+        let seeds: Vec<u64> = runs.iter().map(|run| run["seed"].as_u64().unwrap()).collect();
+        assert_eq!(seeds, (40..40 + RUNS as u64).collect::<Vec<_>>(), "{}", game);
+
+        let scores: Vec<f64> = runs.iter().map(|run| run["score"].as_f64().unwrap()).collect();
+        let mean = scores.iter().sum::<f64>() / RUNS as f64;
+        assert!((summary["score"]["mean"].as_f64().unwrap() - mean).abs() < 1e-9, "{}", game);
+        assert_eq!(summary["score"]["max"].as_f64(), scores.iter().copied().reduce(f64::max), "{}", game);
+
+        let total = |counts: &Value| counts.as_object().unwrap().values().map(|n| n.as_u64().unwrap()).sum::<u64>();
+        assert_eq!(total(&summary["score_histogram"]), RUNS as u64, "{}", game);
+        assert_eq!(total(&summary["causes"]), RUNS as u64, "{}", game);
+        assert_eq!(summary["maze"].is_object(), game == "pacman");
+    }
+}
+
+#[test]
+fn threads_dont_change_the_runs() {
+    let one = simulate(&["game=snake", "board=8", "--threads", "1"]);
+    let three = simulate(&["game=snake", "board=8", "--threads", "3"]);
+    assert_eq!(one["runs"], three["runs"]);
+}
@@ -0,0 +1,115 @@
+//! Golden display lists for each game's `render`: a seeded game, played by
+//! the greedy bot for a number of ticks, must draw exactly what's in
+//! `tests/golden/<name>.txt`. After an intended change to how a game looks,
+//! regenerate them with `UPDATE_GOLDEN=1 cargo test --test snapshots` and
+//! review the diff.
+
+use std::fs;
+use std::path::PathBuf;
+
+use wasm_games::{agent, new_sim, Command, DisplayList, TICK};
+
+mod common;
+
+use common::config;
+
+/// What `options` (query-string pairs) draw after `ticks` ticks of play.
+fn frame(options: &str, ticks: u32) -> String {
+    let config = config(options);
+    let mut sim = new_sim(&config);
+    let mut bot = agent("greedy", 0).unwrap();
+    for _ in 0..ticks {
+        if let Some(command) = bot.act(&sim.view()) {
+            sim.command(command);
+        }
+        sim.update(TICK);
+        sim.take_events();
+    }
+    DisplayList::record(&sim.view(), config.style()).to_string()
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("no golden file {}; run with UPDATE_GOLDEN=1 to create it", path.display()));
+    if expected != actual {
+        let line = expected.lines().zip(actual.lines()).position(|(a, b)| a != b);
+        panic!(
+            "{} differs from {} (first difference at line {}); run with UPDATE_GOLDEN=1 to accept",
+            name,
+            path.display(),
+            line.map_or("end".to_string(), |line| (line + 1).to_string()),
+        );
+    }
+}
+
+#[test]
+fn flappy_title() {
+    assert_golden("flappy_title", &frame("game=flappy&seed=1", 0));
+}
+
+#[test]
+fn flappy_mid_run() {
+    assert_golden("flappy_mid_run", &frame("game=flappy&seed=1", 300));
+}
+
+/// One flap, then the bird falls to the ground.
+fn flappy_fall(options: &str) -> String {
+    let config = config(options);
+    let mut sim = new_sim(&config);
+    sim.command(Command::Flap(0));
+    for _ in 0..240 {
+        sim.update(TICK);
+    }
+    DisplayList::record(&sim.view(), config.style()).to_string()
+}
+
+#[test]
+fn flappy_game_over() {
+    assert_golden("flappy_game_over", &flappy_fall("game=flappy&seed=1"));
+}
+
+#[test]
+fn flappy_game_over_in_spanish() {
+    assert_golden("flappy_game_over_es", &flappy_fall("game=flappy&seed=1&lang=es-MX,en"));
+}
+
+#[test]
+fn snake_mid_run() {
+    assert_golden("snake_mid_run", &frame("game=snake&seed=1", 600));
+}
+
+#[test]
+fn snake_two_players() {
+    assert_golden("snake_two_players", &frame("game=snake&seed=2&players=2&board=12", 120));
+}
+
+#[test]
+fn pacman_mid_run() {
+    assert_golden("pacman_mid_run", &frame("game=pacman&seed=1", 600));
+}
+
+#[test]
+fn pacman_versus() {
+    assert_golden("pacman_versus", &frame("game=pacman&seed=3&players=2&maze=open", 120));
+}
+
+#[test]
+fn pacman_neon_theme() {
+    assert_golden("pacman_neon_theme", &frame("game=pacman&seed=1&theme=neon", 600));
+}
+
+#[test]
+fn pacman_shape_cues() {
+    assert_golden("pacman_shape_cues", &frame("game=pacman&seed=1&theme=deuteranopia&cues=on", 600));
+}
+
+#[test]
+fn snake_shape_cues() {
+    assert_golden("snake_shape_cues", &frame("game=snake&seed=2&players=2&board=12&theme=contrast&cues=on", 120));
+}
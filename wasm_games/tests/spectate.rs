@@ -0,0 +1,107 @@
+//! Spectating: a keyframe and the deltas after it rebuild every frame of a
+//! live game exactly, and a viewer that misses a delta stops following until
+//! the next keyframe rather than showing a wrong frame.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use wasm_games::{new_sim, Command, Publisher, Sim, SpectateMessage, View, Viewer, TICK};
+
+mod common;
+
+use common::config;
+
+const TICKS: u32 = 600;
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+fn sim(options: &str) -> Box<dyn Sim> {
+    new_sim(&config(options))
+}
+
+/// Advances `sim` a tick, now and then turning or flapping at random.
+fn step(sim: &mut dyn Sim, rng: &mut StdRng) {
+    if rng.gen_bool(0.1) {
+        sim.command(match sim.view() {
+            View::Flappy(_) => Command::Flap(0),
+            View::Snake(_) | View::Pacman(_) => {
+                let (dx, dy) = DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())];
+                Command::Steer(0, dx, dy)
+            }
+        });
+    }
+    sim.update(TICK);
+}
+
+/// Steps `sim` until its frame changes, and returns the message for it.
+fn next_message(sim: &mut dyn Sim, rng: &mut StdRng, publisher: &mut Publisher) -> SpectateMessage {
+    loop {
+        step(sim, rng);
+        if let Some(message) = publisher.publish(&sim.view()) {
+            return message;
+        }
+    }
+}
+
+/// Whether `patch` updates some array by index rather than resending it.
+fn patches_by_index(patch: &Value) -> bool {
+    match patch {
+        Value::Object(changes) => changes
+            .iter()
+            .any(|(key, change)| key.parse::<usize>().is_ok() || patches_by_index(change)),
+        _ => false,
+    }
+}
+
+#[test]
+fn deltas_rebuild_every_frame() {
+    for options in ["game=flappy&seed=1", "game=snake&seed=2", "game=pacman&seed=3"] {
+        let (mut sim, mut rng) = (sim(options), StdRng::seed_from_u64(4));
+        let (mut publisher, mut viewer) = (Publisher::new(), Viewer::new());
+        let (mut deltas, mut indexed) = (0, 0);
+
+        for tick in 0..TICKS {
+            step(sim.as_mut(), &mut rng);
+            let view = sim.view();
+            if let Some(message) = publisher.publish(&view) {
+                if let SpectateMessage::Delta { patch, .. } = &message {
+                    deltas += 1;
+                    indexed += patches_by_index(patch) as u32;
+                }
+                viewer.receive(message);
+            }
+            assert!(viewer.in_sync(), "{} tick {}", options, tick);
+            assert_eq!(viewer.view(), Some(&view), "{} tick {}", options, tick);
+        }
+        assert!(deltas > 0, "{}", options);
+        if options.contains("pacman") {
+            assert!(indexed > 0, "eaten pellets should patch the maze by index");
+        }
+    }
+}
+
+#[test]
+fn skipped_delta_waits_for_the_next_keyframe() {
+    let (mut sim, mut rng) = (sim("game=pacman&seed=5"), StdRng::seed_from_u64(6));
+    let (mut publisher, mut viewer) = (Publisher::new(), Viewer::new());
+    for _ in 0..10 {
+        viewer.receive(next_message(sim.as_mut(), &mut rng, &mut publisher));
+    }
+    let shown = viewer.view().cloned();
+
+    // ✅ One lost on the way; the ones after it can't be applied
+    let lost = next_message(sim.as_mut(), &mut rng, &mut publisher);
+    assert!(matches!(lost, SpectateMessage::Delta { .. }));
+    for _ in 0..5 {
+        viewer.receive(next_message(sim.as_mut(), &mut rng, &mut publisher));
+        assert!(!viewer.in_sync());
+        assert_eq!(viewer.view().cloned(), shown);
+    }
+
+    publisher.request_keyframe();
+    step(sim.as_mut(), &mut rng);
+    let keyframe = publisher.publish(&sim.view()).unwrap();
+    assert!(matches!(keyframe, SpectateMessage::Keyframe { .. }));
+    viewer.receive(keyframe);
+    assert!(viewer.in_sync());
+    assert_eq!(viewer.view(), Some(&sim.view()));
+}
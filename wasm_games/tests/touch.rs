@@ -0,0 +1,23 @@
+//! Touch controls are validated like the rest of the settings. How swipes,
+//! the D-pad and taps play is tested beside the gestures in `src/touch.rs`.
+
+use wasm_games::GameConfig;
+
+mod common;
+
+use common::config;
+
+#[test]
+fn touch_options_are_validated() {
+    let touch = config("game=snake").touch();
+    assert!(!touch.dpad);
+    assert!(touch.swipe_threshold > 0.0);
+
+    let touch = config("game=pacman&swipe=40&dpad=on").touch();
+    assert_eq!((touch.swipe_threshold, touch.dpad), (40.0, true));
+
+    for (key, value) in [("swipe", "0"), ("swipe", "far"), ("dpad", "maybe")] {
+        let err = GameConfig::from_lookup(|k| (k == key).then(|| value.to_string())).unwrap_err();
+        assert!(err.to_string().contains(key), "{}={}", key, value);
+    }
+}